@@ -64,5 +64,5 @@ fn main() {
 
     println!("Db saved");
 
-    db.save("./examples/db.sfn")
+    db.save("./examples/db.sfn").unwrap();
 }
@@ -33,13 +33,22 @@
 /// Formatter for tables and types
 use core::fmt;
 /// FileSystem utilities for saving and loading database
-use std::{fs::File, io::Write};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Write},
+};
 /// Database types
-use table::{Table, TableRow, TypeDefs};
+use migration::{AppliedMigration, Migration};
+use table::{MigrationError, Table, TableRow, TypeDefs};
+/// Database migrations
+pub mod migration;
 /// Database table
 pub mod table;
 /// Database utils
 pub mod utils;
+/// Hot reload of database files
+pub mod watch;
 
 /// Integrity error
 #[derive(Debug, Clone)]
@@ -51,6 +60,185 @@ impl fmt::Display for LoadError {
     }
 }
 
+/// Io error while saving database
+#[derive(Debug, Clone)]
+pub struct SaveError;
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Failed to save db to file")
+    }
+}
+
+/// Error while writing database as JSON
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone)]
+pub struct JsonError(String);
+
+#[cfg(feature = "serde")]
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Failed to write db as json: {}", self.0)
+    }
+}
+
+/// Table metadata returned by [`Database::list_tables`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableInfo {
+    /// Table name
+    pub name: String,
+    /// Number of rows
+    pub row_count: usize,
+    /// Number of columns
+    pub column_count: usize,
+    /// Whether table has an index
+    pub has_index: bool,
+}
+
+/// Fluent table creation, returned by [`Database::build_table`]
+///
+/// Unique and default constraints are saved with the table by [`Database::save`]
+/// ```
+/// use safe_en::{table::TypeDefs, Database};
+/// let path = std::env::temp_dir().join("safe_en_constraints.sfn");
+/// let path = path.to_str().unwrap();
+/// let mut db = Database::new();
+/// db.build_table("users")
+///     .column("email", TypeDefs::String)
+///     .column("active", TypeDefs::Bool)
+///     .unique("email")
+///     .default("active", true)
+///     .finish()
+///     .unwrap();
+/// db.table_unwrap("users").insert(vec!["john@example.com".into()]).unwrap();
+/// db.save(path).unwrap();
+///
+/// let mut loaded = Database::load(path).unwrap();
+/// let users = loaded.table_unwrap("users");
+/// assert!(users.insert(vec!["john@example.com".into()]).is_err());
+/// users.insert(vec!["jane@example.com".into()]).unwrap();
+/// assert!(users.get_all()[1].row("active").is(true));
+/// ```
+pub struct TableBuilder<'a> {
+    database: &'a mut Database,
+    name: String,
+    rows: Vec<TableRow>,
+    unique: Vec<String>,
+    defaults: Vec<(String, table::SafeType)>,
+}
+
+impl<'a> TableBuilder<'a> {
+    /// Adds a column
+    /// ## Parameters
+    /// * `key` - Column name
+    /// * `rtype` - Column type
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::TypeDefs, Database};
+    /// let mut db = Database::new();
+    /// db.build_table("users").column("id", TypeDefs::I64).finish().unwrap();
+    /// ```
+    pub fn column(mut self, key: &str, rtype: TypeDefs) -> Self {
+        self.rows.push(TableRow::new(key, rtype));
+        self
+    }
+
+    /// Rejects inserts and updates repeating a value already present in the column
+    /// ## Parameters
+    /// * `key` - Column name
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::TypeDefs, Database};
+    /// let mut db = Database::new();
+    /// db.build_table("users")
+    ///     .column("email", TypeDefs::String)
+    ///     .unique("email")
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let users = db.table("users").unwrap();
+    /// users.insert(vec!["john@example.com".into()]).unwrap();
+    /// users.insert(vec!["jane@example.com".into()]).unwrap();
+    /// assert!(users.insert(vec!["john@example.com".into()]).is_err());
+    /// assert!(users
+    ///     .set_column_value_where(|x| x.row("email").is("jane@example.com"), "email", "john@example.com".into())
+    ///     .is_err());
+    /// assert_eq!(users.get_all().len(), 2);
+    /// assert!(users.get_all()[1].row("email").is("jane@example.com"));
+    /// ```
+    pub fn unique(mut self, key: &str) -> Self {
+        self.unique.push(key.to_string());
+        self
+    }
+
+    /// Sets the value used when an insert leaves out the column
+    ///
+    /// Only trailing columns can be left out of [`Table::insert`]
+    /// ## Parameters
+    /// * `key` - Column name
+    /// * `value` - Default value
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::TypeDefs, Database};
+    /// let mut db = Database::new();
+    /// db.build_table("users")
+    ///     .column("name", TypeDefs::String)
+    ///     .column("active", TypeDefs::Bool)
+    ///     .default("active", true)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let users = db.table("users").unwrap();
+    /// users.insert(vec!["John".into()]).unwrap();
+    /// assert!(users.get_all()[0].row("active").is(true));
+    /// ```
+    pub fn default<T: Into<table::SafeType>>(mut self, key: &str, value: T) -> Self {
+        self.defaults.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Creates the table with [`Database::create_table`]
+    /// ## Returns
+    /// * [`Ok(())`]
+    /// * [`Err(())`] If a table with same name already exists, or a constraint names an unknown column or a default has the wrong type
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::TypeDefs, Database};
+    /// let mut db = Database::new();
+    /// let result = db
+    ///     .build_table("users")
+    ///     .column("id", TypeDefs::I64)
+    ///     .default("id", "zero")
+    ///     .finish();
+    /// assert!(result.is_err());
+    /// assert!(db.table("users").is_none());
+    /// ```
+    // Same error as `Database::create_table`, which it wraps
+    #[allow(clippy::result_unit_err)]
+    pub fn finish(self) -> Result<(), ()> {
+        let header_type = |key: &String| {
+            self.rows
+                .iter()
+                .find(|x| &x.key == key)
+                .map(|x| x.rtype.clone())
+        };
+        if self.unique.iter().any(|key| header_type(key).is_none())
+            || self
+                .defaults
+                .iter()
+                .any(|(key, value)| header_type(key) != Some(value.get_type_def()))
+        {
+            return Err(());
+        }
+        self.database.create_table(&self.name, self.rows)?;
+        let table = self.database.table_unwrap(&self.name);
+        table.unique_columns = self.unique;
+        table.column_defaults = self.defaults.into_iter().collect();
+        Ok(())
+    }
+}
+
 /// Database struct
 pub struct Database {
     /// Database name
@@ -59,6 +247,8 @@ pub struct Database {
     size: usize,
     /// Database tables
     tables: Vec<table::Table>,
+    /// Migrations applied in this session, kept for their backward steps
+    migrations: Vec<Migration>,
 }
 
 impl Database {
@@ -68,9 +258,25 @@ impl Database {
             tables: Vec::new(),
             name: "".to_string(),
             size: 0,
+            migrations: Vec::new(),
         }
     }
 
+    /// Creates a new database with given name
+    /// ## Parameters
+    /// * `name` - The name of the database
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// let db = Database::with_name("myapp");
+    /// assert_eq!(db.get_name(), "myapp");
+    /// ```
+    pub fn with_name(name: &str) -> Self {
+        let mut db = Database::new();
+        db.set_name(name);
+        db
+    }
+
     /// Loads a database from a file
     /// ## Errors
     /// Returns a `LoadError` if integrity checks fail
@@ -81,6 +287,66 @@ impl Database {
     /// use safe_en::Database;
     /// let db = Database::load("db.sfn");
     /// ```
+    /// Strings longer than 255 bytes survive a save and load
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let path = std::env::temp_dir().join("safe_en_long_string.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let long_text = "a".repeat(300);
+    /// let mut db = Database::new();
+    /// db.create_table("notes", vec![TableRow::new("text", TypeDefs::String)]).unwrap();
+    /// db.table_unwrap("notes").insert(vec![long_text.clone().into()]).unwrap();
+    /// db.save(path).unwrap();
+    ///
+    /// let mut loaded = Database::load(path).unwrap();
+    /// let row = loaded.table_unwrap("notes").get_at(0).unwrap();
+    /// assert_eq!(row.get("text").unwrap().get::<String>(), long_text);
+    /// ```
+    /// I8 values keep their sign and bounds
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let path = std::env::temp_dir().join("safe_en_i8.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.create_table("levels", vec![TableRow::new("level", TypeDefs::I8)]).unwrap();
+    /// for level in [i8::MIN, -1, 0, i8::MAX] {
+    ///     db.table_unwrap("levels").insert(vec![level.into()]).unwrap();
+    /// }
+    /// db.save(path).unwrap();
+    ///
+    /// let mut loaded = Database::load(path).unwrap();
+    /// let levels: Vec<i8> = loaded
+    ///     .table_unwrap("levels")
+    ///     .get_all()
+    ///     .iter()
+    ///     .map(|x| x.get("level").unwrap().get())
+    ///     .collect();
+    /// assert_eq!(levels, vec![i8::MIN, -1, 0, i8::MAX]);
+    ///
+    /// // Size prefix of the last I8 value is corrupted
+    /// let mut bytes = std::fs::read(path).unwrap();
+    /// let prefix = bytes.len() - 2;
+    /// bytes[prefix] = 8;
+    /// std::fs::write(path, bytes).unwrap();
+    /// assert!(Database::load(path).is_err());
+    /// ```
+    /// Tuple element count must match the header
+    /// ```
+    /// use safe_en::{Database, table::{SafeType, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("points", vec![
+    ///     TableRow::new("point", TypeDefs::Tuple(vec![TypeDefs::I8, TypeDefs::I8])),
+    /// ]).unwrap();
+    /// db.table_unwrap("points").insert(vec![SafeType::tuple(vec![1_i8.into(), 2_i8.into()])]).unwrap();
+    /// let mut bytes = vec![];
+    /// db.save_to_writer(&mut bytes).unwrap();
+    /// assert!(Database::new().load_from_reader(&mut bytes.as_slice()).is_ok());
+    ///
+    /// // Element count of the tuple, followed by two I8 values
+    /// let prefix = bytes.len() - 5;
+    /// bytes[prefix] = 3;
+    /// assert!(Database::new().load_from_reader(&mut bytes.as_slice()).is_err());
+    /// ```
     pub fn load(path: &str) -> Result<Self, LoadError> {
         let mut db = Database::new();
         match db.load_file(path) {
@@ -89,6 +355,119 @@ impl Database {
         }
     }
 
+    /// Loads a database from a file and reloads it in a background thread when the file changes
+    ///
+    /// File modification time and size are polled every [`watch::DEFAULT_INTERVAL_MS`] milliseconds
+    /// ## Errors
+    /// Returns a `LoadError` if the first load fails
+    /// ## Parameters
+    /// * `path` - The path to the file
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// let path = std::env::temp_dir().join("safe_en_watch.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.set_name("first");
+    /// db.save(path).unwrap();
+    ///
+    /// let handle = Database::watch(path).unwrap();
+    /// assert_eq!(handle.current().read().unwrap().get_name(), "first");
+    ///
+    /// // Some file systems only keep modification time in 2 second steps
+    /// std::thread::sleep(std::time::Duration::from_millis(2100));
+    /// db.set_name("second");
+    /// db.save(path).unwrap();
+    /// let mut reloaded = false;
+    /// for _ in 0..50 {
+    ///     std::thread::sleep(std::time::Duration::from_millis(100));
+    ///     if handle.current().read().unwrap().get_name() == "second" {
+    ///         reloaded = true;
+    ///         break;
+    ///     }
+    /// }
+    /// assert!(reloaded);
+    /// ```
+    pub fn watch(path: &str) -> Result<watch::WatchHandle, LoadError> {
+        Database::watch_with_options(path, watch::WatchOptions::default())
+    }
+
+    /// Same as [`Database::watch`] with configurable poll interval and reload callbacks
+    /// ## Errors
+    /// Returns a `LoadError` if the first load fails, later failures are passed to `on_error`
+    /// ## Parameters
+    /// * `path` - The path to the file
+    /// * `options` - The watch options
+    /// ## Example
+    /// ```
+    /// use safe_en::{watch::WatchOptions, Database};
+    /// use std::sync::mpsc;
+    /// let path = std::env::temp_dir().join("safe_en_watch_options.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.set_name("first");
+    /// db.save(path).unwrap();
+    ///
+    /// let (sender, reloads) = mpsc::channel();
+    /// let handle = Database::watch_with_options(
+    ///     path,
+    ///     WatchOptions {
+    ///         interval_ms: 50,
+    ///         on_reload: Box::new(move |db| sender.send(db.get_name()).unwrap()),
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// .unwrap();
+    ///
+    /// std::thread::sleep(std::time::Duration::from_millis(20));
+    /// db.set_name("second");
+    /// db.save(path).unwrap();
+    /// let name = reloads.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+    /// assert_eq!(name, "second");
+    /// drop(handle);
+    /// ```
+    /// Callbacks can read the shared state of the handle
+    /// ```
+    /// use safe_en::{watch::WatchOptions, Database};
+    /// use std::sync::{mpsc, Arc, Mutex, RwLock};
+    /// let path = std::env::temp_dir().join("safe_en_watch_shared.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.set_name("first");
+    /// db.save(path).unwrap();
+    ///
+    /// let shared: Arc<Mutex<Option<Arc<RwLock<Database>>>>> = Arc::new(Mutex::new(None));
+    /// let in_callback = shared.clone();
+    /// let (sender, reloads) = mpsc::channel();
+    /// let handle = Database::watch_with_options(
+    ///     path,
+    ///     WatchOptions {
+    ///         interval_ms: 50,
+    ///         on_reload: Box::new(move |_| {
+    ///             if let Some(current) = &*in_callback.lock().unwrap() {
+    ///                 sender.send(current.read().unwrap().get_name()).unwrap();
+    ///             }
+    ///         }),
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// .unwrap();
+    /// *shared.lock().unwrap() = Some(handle.current());
+    ///
+    /// db.set_name("second");
+    /// db.save(path).unwrap();
+    /// let name = reloads.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+    /// assert_eq!(name, "second");
+    /// drop(handle);
+    /// ```
+    pub fn watch_with_options(
+        path: &str,
+        options: watch::WatchOptions,
+    ) -> Result<watch::WatchHandle, LoadError> {
+        let db = Database::load(path)?;
+        watch::WatchHandle::spawn(path, db, options)
+    }
+
     ///Sets name of the database
     /// ## Parameters
     /// * `name` - The name of the database
@@ -135,6 +514,101 @@ impl Database {
         self.tables.len()
     }
 
+    ///Returns metadata of every table in the database
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, TableInfo, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("id", TypeDefs::I64),
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]).unwrap();
+    /// db.table("users").unwrap().insert(vec![1_i64.into(), "ahmet@mail.com".into()]).unwrap();
+    /// assert_eq!(db.list_tables(), vec![TableInfo {
+    ///     name: "users".to_string(),
+    ///     row_count: 1,
+    ///     column_count: 2,
+    ///     has_index: false,
+    /// }]);
+    /// ```
+    pub fn list_tables(&self) -> Vec<TableInfo> {
+        self.tables
+            .iter()
+            .map(|table| TableInfo {
+                name: table.name.clone(),
+                row_count: table.columns.len(),
+                column_count: table.headers.len(),
+                has_index: table.has_index(),
+            })
+            .collect()
+    }
+
+    ///Calls `f` with name and reference of every table
+    /// ## Parameters
+    /// * `f` - Function called for each table
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("posts", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// let mut names = vec![];
+    /// db.for_each_table(|name, _| names.push(name.to_string()));
+    /// assert_eq!(names, vec!["users", "posts"]);
+    /// ```
+    pub fn for_each_table<F: FnMut(&str, &Table)>(&self, mut f: F) {
+        for table in &self.tables {
+            f(&table.name, table);
+        }
+    }
+
+    ///Calls `f` with name and mutable reference of every table
+    /// ## Parameters
+    /// * `f` - Function called for each table
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("posts", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.for_each_table_mut(|_, table| table.insert(vec![1_i64.into()]).unwrap());
+    /// assert_eq!(db.table("posts").unwrap().get_all().len(), 1);
+    /// ```
+    pub fn for_each_table_mut<F: FnMut(&str, &mut Table)>(&mut self, mut f: F) {
+        for table in self.tables.iter_mut() {
+            let name = table.name.clone();
+            f(&name, table);
+        }
+    }
+
+    ///Returns checksum of the database, chaining database name, table names and table checksums
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("id", TypeDefs::I64),
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]).unwrap();
+    /// let checksum = db.compute_checksum();
+    /// assert_eq!(checksum, db.compute_checksum());
+    ///
+    /// db.table("users").unwrap().insert(vec![1_i64.into(), "ahmet@mail.com".into()]).unwrap();
+    /// assert_ne!(checksum, db.compute_checksum());
+    /// ```
+    pub fn compute_checksum(&self) -> u64 {
+        let mut bytes = vec![];
+        utils::extend_bytes_from_raw_type(&mut bytes, &utils::type_to_bytes(self.name.clone()));
+        for table in self.tables.iter() {
+            utils::extend_bytes_from_raw_type(
+                &mut bytes,
+                &utils::type_to_bytes(table.name.clone()),
+            );
+            bytes.extend(table.compute_checksum().to_le_bytes());
+        }
+        utils::fnv1a_64(&bytes)
+    }
+
     /// Get query
     /// ## Parameters
     /// * `table` - The name of the table
@@ -178,6 +652,44 @@ impl Database {
             .unwrap()
     }
 
+    /// Get table, creating it first if it does not exist
+    /// ## Parameters
+    /// * `name` - Table name
+    /// * `rows` - Table rows, ignored if table already exists
+    /// ## Returns
+    /// * [`Ok(&mut Table)`]
+    /// * [`Err(())`] If table does not exist and is rejected by [`Database::create_table`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// let rows = vec![
+    ///   TableRow::new("id", TypeDefs::I64),
+    ///   TableRow::new("email", TypeDefs::String),
+    /// ];
+    /// db.get_or_insert_table("users", rows.clone())
+    ///   .unwrap()
+    ///   .insert(vec![1_i64.into(), "ahmet@mail.com".into()])
+    ///   .unwrap();
+    /// assert_eq!(db.get_or_insert_table("users", rows).unwrap().get_all().len(), 1);
+    /// assert_eq!(db.get_table_count(), 1);
+    ///
+    /// let wide = vec![TableRow::new("point", TypeDefs::Tuple(vec![TypeDefs::I8; 256]))];
+    /// assert!(db.get_or_insert_table("points", wide).is_err());
+    /// ```
+    // Same error as `Database::create_table`, which it wraps
+    #[allow(clippy::result_unit_err)]
+    pub fn get_or_insert_table(
+        &mut self,
+        table_name: &str,
+        rows: Vec<TableRow>,
+    ) -> Result<&mut Table, ()> {
+        if !self.tables.iter().any(|x| x.name == table_name) {
+            self.create_table(table_name, rows)?;
+        }
+        Ok(self.table_unwrap(table_name))
+    }
+
     /// Removes table
     /// ## Parameters
     /// * `name` - Table name
@@ -215,7 +727,7 @@ impl Database {
     /// * `rows` - Table rows
     /// ## Returns
     /// * [`Ok(())`]
-    /// * [`Err(())`] If a table with same name already exists
+    /// * [`Err(())`] If a table with same name already exists or a tuple column has more than 255 elements
     /// ## Example
     /// ```
     /// use safe_en::{
@@ -229,13 +741,15 @@ impl Database {
     ///      TableRow::new("id", TypeDefs::I64),
     ///      TableRow::new("email", TypeDefs::String),
     ///    ]).unwrap();
+    ///
+    /// let wide = TypeDefs::Tuple(vec![TypeDefs::I8; 256]);
+    /// assert!(db.create_table("points", vec![TableRow::new("point", wide)]).is_err());
     /// ```
     pub fn create_table(&mut self, table_name: &str, rows: Vec<TableRow>) -> Result<(), ()> {
-        let table = table::Table {
-            name: table_name.to_owned(),
-            headers: rows,
-            columns: vec![],
-        };
+        if rows.iter().any(|x| !x.rtype.fits_type_id()) {
+            return Err(());
+        }
+        let table = table::Table::new(table_name, rows);
         if self.tables.iter().find(|x| x.name == table_name).is_some() {
             return Err(());
         } else {
@@ -244,6 +758,186 @@ impl Database {
         }
     }
 
+    /// Starts a fluent table creation, see [`TableBuilder`]
+    /// ## Parameters
+    /// * `name` - Table name
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::TypeDefs, Database};
+    /// let mut db = Database::new();
+    /// db.build_table("users")
+    ///     .column("id", TypeDefs::I64)
+    ///     .column("email", TypeDefs::String)
+    ///     .unique("email")
+    ///     .finish()
+    ///     .unwrap();
+    /// assert_eq!(db.table("users").unwrap().get_headers().len(), 2);
+    /// ```
+    pub fn build_table(&mut self, table_name: &str) -> TableBuilder<'_> {
+        TableBuilder {
+            database: self,
+            name: table_name.to_string(),
+            rows: Vec::new(),
+            unique: Vec::new(),
+            defaults: Vec::new(),
+        }
+    }
+
+    /// Applies a migration and records it in the `__migrations` table
+    /// ## Parameters
+    /// * `migration` - Migration to apply
+    /// ## Returns
+    /// * [`Ok(())`]
+    /// * [`Err(MigrationError)`] If migration is already applied or its forward step fails
+    /// ## Example
+    /// ```
+    /// use safe_en::{
+    ///     migration::Migration,
+    ///     table::{MigrationError, TableRow, TypeDefs},
+    ///     Database,
+    /// };
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![TableRow::new("name", TypeDefs::String)]).unwrap();
+    ///
+    /// let add_email = || {
+    ///     Migration::new("add_email_column").up(|db| {
+    ///         db.table_unwrap("users")
+    ///             .ensure_columns(&[("email", TypeDefs::String)])
+    ///     })
+    /// };
+    /// db.apply_migration(add_email()).unwrap();
+    /// assert_eq!(
+    ///     db.apply_migration(add_email()).err(),
+    ///     Some(MigrationError::AlreadyApplied("add_email_column".to_string()))
+    /// );
+    /// assert_eq!(db.table("__migrations").unwrap().get_all().len(), 1);
+    /// ```
+    pub fn apply_migration(&mut self, migration: Migration) -> Result<(), MigrationError> {
+        let already_applied = match self.table(migration::MIGRATIONS_TABLE) {
+            Some(table) => !table
+                .get_where(|x| x.row("name").is(migration.get_name()))
+                .is_empty(),
+            None => false,
+        };
+        if already_applied {
+            return Err(MigrationError::AlreadyApplied(migration.name));
+        }
+
+        if let Some(up) = &migration.up {
+            up(self)?;
+        }
+
+        let applied_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or(0);
+        self.get_or_insert_table(
+            migration::MIGRATIONS_TABLE,
+            vec![
+                TableRow::new("name", TypeDefs::String),
+                TableRow::new("applied_at", TypeDefs::U64),
+            ],
+        )
+        .map_err(|_| MigrationError::Failed("Could not create migrations table".to_string()))?
+        .insert(vec![migration.name.clone().into(), applied_at.into()])
+        .map_err(|e| MigrationError::Failed(e.join(", ")))?;
+        self.migrations.push(migration);
+        Ok(())
+    }
+
+    /// Returns migrations recorded in the `__migrations` table, in the order they were applied
+    /// ## Example
+    /// ```
+    /// use safe_en::{migration::Migration, Database};
+    /// let mut db = Database::new();
+    /// assert!(db.migration_status().is_empty());
+    /// db.apply_migration(Migration::new("init")).unwrap();
+    /// assert_eq!(db.migration_status()[0].name, "init");
+    /// ```
+    pub fn migration_status(&self) -> Vec<AppliedMigration> {
+        match self
+            .tables
+            .iter()
+            .find(|x| x.name == migration::MIGRATIONS_TABLE)
+        {
+            Some(table) => table
+                .iter()
+                .map(|x| AppliedMigration {
+                    name: x.row("name").get_value().unwrap_or_default(),
+                    applied_at: x.row("applied_at").get_value().unwrap_or_default(),
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Rolls back the last applied migration by running its backward step
+    ///
+    /// Only migrations applied with this [`Database`] instance can be rolled back,
+    /// backward steps are not saved to file
+    /// ## Returns
+    /// * [`Ok(String)`] Name of the rolled back migration
+    /// * [`Err(MigrationError)`] If there is nothing to roll back or the backward step fails
+    /// ## Example
+    /// ```
+    /// use safe_en::{
+    ///     migration::Migration,
+    ///     table::{TableRow, TypeDefs},
+    ///     Database,
+    /// };
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![TableRow::new("name", TypeDefs::String)]).unwrap();
+    /// db.apply_migration(
+    ///     Migration::new("add_email_column")
+    ///         .up(|db| db.table_unwrap("users").ensure_columns(&[("email", TypeDefs::String)]))
+    ///         .down(|db| db.table_unwrap("users").retain_columns(&["name"])),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(db.rollback_last_migration().unwrap(), "add_email_column");
+    /// assert!(!db.table("users").unwrap().has_column("email"));
+    /// assert!(db.migration_status().is_empty());
+    /// assert!(db.rollback_last_migration().is_err());
+    /// ```
+    pub fn rollback_last_migration(&mut self) -> Result<String, MigrationError> {
+        let last = match self.migration_status().pop() {
+            Some(e) => e,
+            None => {
+                return Err(MigrationError::Failed(
+                    "There is no migration to roll back".to_string(),
+                ))
+            }
+        };
+        let migration = match self.migrations.pop() {
+            Some(e) if e.name == last.name => e,
+            other => {
+                self.migrations.extend(other);
+                return Err(MigrationError::Failed(format!(
+                    "Migration '{}' was not applied in this session",
+                    last.name
+                )));
+            }
+        };
+
+        if let Some(down) = &migration.down {
+            if let Err(e) = down(self) {
+                self.migrations.push(migration);
+                return Err(e);
+            }
+        } else {
+            let name = migration.name.clone();
+            self.migrations.push(migration);
+            return Err(MigrationError::Failed(format!(
+                "Migration '{}' has no down step",
+                name
+            )));
+        }
+
+        self.table_unwrap(migration::MIGRATIONS_TABLE)
+            .remove_where(|x| x.row("name").is(last.name.as_str()));
+        Ok(migration.name)
+    }
+
     /// Load database from file
     /// ## Parameters
     /// * `path` - The path to the file
@@ -257,44 +951,91 @@ impl Database {
             Ok(it) => it,
             Err(_) => return Err(LoadError),
         };
-        let db_name: String = utils::read_data(&mut file, TypeDefs::String).get();
-        let table_len: u64 = utils::read_data(&mut file, TypeDefs::U64).get();
-        self.set_name(&db_name);
+        self.load_from_reader(&mut file)
+    }
+
+    /// Load database from a reader, tables are added to this database
+    /// ## Errors
+    /// Returns a `LoadError` if integrity checks fail, input is truncated or corrupted, was written in another format version
+    /// or a table with the same name already exists. Database is left untouched on error
+    /// ## Parameters
+    /// * `reader` - Source of bytes written by [`Database::save_to_writer`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::with_name("memory");
+    /// db.create_table("users", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.table_unwrap("users").insert(vec![1_i64.into()]).unwrap();
+    ///
+    /// let mut bytes = vec![];
+    /// db.save_to_writer(&mut bytes).unwrap();
+    ///
+    /// let mut loaded = Database::new();
+    /// loaded.load_from_reader(&mut bytes.as_slice()).unwrap();
+    /// assert_eq!(loaded.get_name(), "memory");
+    /// assert_eq!(loaded.table_unwrap("users").get_all().len(), 1);
+    ///
+    /// let mut partial = Database::new();
+    /// assert!(partial.load_from_reader(&mut &bytes[..bytes.len() - 4]).is_err());
+    /// assert_eq!(partial.get_table_count(), 0);
+    ///
+    /// // Name claims to be u64::MAX bytes long
+    /// let mut huge = bytes[..5].to_vec();
+    /// huge.push(8);
+    /// huge.extend(u64::MAX.to_le_bytes());
+    /// assert!(partial.load_from_reader(&mut huge.as_slice()).is_err());
+    ///
+    /// // Files start with a magic and format version, files without them are rejected
+    /// assert_eq!(&bytes[..5], b"SFEN\x02");
+    /// assert!(partial.load_from_reader(&mut &bytes[5..]).is_err());
+    /// ```
+    pub fn load_from_reader(&mut self, mut reader: &mut dyn Read) -> Result<(), LoadError> {
+        // Tables are read into a new database so a failed load leaves this one untouched
+        let mut loaded = Database::new();
+        utils::read_file_header(&mut reader)?;
+        let db_name: String = utils::read_data(&mut reader, TypeDefs::String)?.get();
+        let table_len: u64 = utils::read_data(&mut reader, TypeDefs::U64)?.get();
+        loaded.set_name(&db_name);
         for _ in 0..table_len {
-            let table_name: String = utils::read_data(&mut file, TypeDefs::String).get();
-            let table_headers_len: u64 = utils::read_data(&mut file, TypeDefs::U64).get();
+            let table_name: String = utils::read_data(&mut reader, TypeDefs::String)?.get();
+            let table_headers_len: u64 = utils::read_data(&mut reader, TypeDefs::U64)?.get();
 
             let mut table_rows: Vec<TableRow> = Vec::new();
+            let mut unique = Vec::new();
+            let mut defaults = HashMap::new();
 
             for _ in 0..table_headers_len {
-                let table_header: String = utils::read_data(&mut file, TypeDefs::String).get();
-                let base_header_type: i8 = utils::read_one(&mut file);
-                let second_header_type: i8 = utils::read_one(&mut file);
-                let row = TableRow::new(
-                    &table_header,
-                    TypeDefs::from_base_and_second_layer(
-                        base_header_type as u8,
-                        second_header_type as u8,
-                    ),
-                );
-                table_rows.push(row);
+                let table_header: String = utils::read_data(&mut reader, TypeDefs::String)?.get();
+                let (rtype, flags) = utils::read_header_type(&mut reader)?;
+                if flags & utils::UNIQUE_FLAG != 0 {
+                    unique.push(table_header.clone());
+                }
+                if flags & utils::DEFAULT_FLAG != 0 {
+                    let default = utils::read_data(&mut reader, rtype.clone())?;
+                    defaults.insert(table_header.clone(), default);
+                }
+                table_rows.push(TableRow::new(&table_header, rtype));
             }
 
             //Create table from collected rows
-            match self.create_table(&table_name, table_rows.clone()) {
-                Ok(it) => it,
-                Err(_) => return Err(LoadError),
-            };
+            if self.table(&table_name).is_some()
+                || loaded.create_table(&table_name, table_rows.clone()).is_err()
+            {
+                return Err(LoadError);
+            }
+            let table = loaded.table_unwrap(&table_name);
+            table.unique_columns = unique;
+            table.column_defaults = defaults;
 
-            let table_rows_len: u64 = utils::read_data(&mut file, TypeDefs::U64).get();
+            let table_rows_len: u64 = utils::read_data(&mut reader, TypeDefs::U64)?.get();
 
             for _ in 0..table_rows_len {
                 let mut tables = vec![];
                 for table_row in &table_rows {
-                    let row_value = utils::read_data(&mut file, table_row.rtype.clone());
+                    let row_value = utils::read_data(&mut reader, table_row.rtype.clone())?;
                     tables.push(row_value);
                 }
-                match self.table(&table_name) {
+                match loaded.table(&table_name) {
                     Some(it) => match it.insert(tables.clone()) {
                         Ok(_) => (),
                         Err(_) => return Err(LoadError),
@@ -303,20 +1044,70 @@ impl Database {
                 }
             }
         }
+        self.set_name(&loaded.name);
+        self.tables.append(&mut loaded.tables);
         Ok(())
     }
 
     /// Saves database to file
+    /// ## Errors
+    /// Returns a `SaveError` if file cannot be created or written
     /// ## Parameters
     /// * `path` - The path to the file
     /// ## Example
     /// ```
     /// use safe_en::Database;
     /// let mut db = Database::new();
-    /// db.save("db.sfn");
+    /// db.save("db.sfn").unwrap();
+    /// assert!(db.save("missing_directory/db.sfn").is_err());
     /// ```
-    pub fn save(&self, path: &str) {
-        let mut bytes = vec![];
+    /// Multi-byte chars are saved as their full 4 byte code point
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let path = std::env::temp_dir().join("safe_en_chars.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.create_table("symbols", vec![TableRow::new("symbol", TypeDefs::Char)]).unwrap();
+    /// db.table_unwrap("symbols").insert(vec!['€'.into()]).unwrap();
+    /// db.table_unwrap("symbols").insert(vec!['🎉'.into()]).unwrap();
+    /// db.save(path).unwrap();
+    ///
+    /// let mut loaded = Database::load(path).unwrap();
+    /// let rows = loaded.table_unwrap("symbols").get_all();
+    /// assert_eq!(rows[0].get("symbol").unwrap().get::<char>(), '€');
+    /// assert_eq!(rows[1].get("symbol").unwrap().get::<char>(), '🎉');
+    /// ```
+    pub fn save(&self, path: &str) -> Result<(), SaveError> {
+        let mut file = match File::create(path) {
+            Ok(it) => it,
+            Err(_) => return Err(SaveError),
+        };
+        self.save_to_writer(&mut file)
+    }
+
+    /// Writes database to a writer
+    /// ## Errors
+    /// Returns a `SaveError` if writer fails or a tuple column has more than 255 elements
+    /// ## Parameters
+    /// * `writer` - Destination of the bytes, read back with [`Database::load_from_reader`]
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// let db = Database::with_name("memory");
+    /// let mut bytes = vec![];
+    /// db.save_to_writer(&mut bytes).unwrap();
+    /// assert!(!bytes.is_empty());
+    /// ```
+    pub fn save_to_writer(&self, writer: &mut dyn Write) -> Result<(), SaveError> {
+        if self
+            .tables
+            .iter()
+            .any(|table| table.headers.iter().any(|x| !x.rtype.fits_type_id()))
+        {
+            return Err(SaveError);
+        }
+        let mut bytes = utils::FILE_MAGIC.to_vec();
+        bytes.push(utils::FORMAT_VERSION);
 
         utils::extend_bytes_from_raw_type(&mut bytes, &utils::type_to_bytes(self.name.clone()));
         utils::extend_bytes_from_raw_type(
@@ -339,7 +1130,21 @@ impl Database {
                     &mut bytes,
                     &utils::type_to_bytes(header.key.clone()),
                 );
-                bytes.extend(header.rtype.get_base_and_second_layer());
+                let mut type_id = header.rtype.get_base_and_second_layer();
+                let default = table.column_defaults.get(&header.key);
+                if table.unique_columns.contains(&header.key) {
+                    type_id[0] |= utils::UNIQUE_FLAG;
+                }
+                if default.is_some() {
+                    type_id[0] |= utils::DEFAULT_FLAG;
+                }
+                bytes.extend(type_id);
+                if let Some(default) = default {
+                    utils::extend_bytes_from_raw_type(
+                        &mut bytes,
+                        &utils::type_to_bytes(default.get_type()),
+                    );
+                }
             }
 
             utils::extend_bytes_from_raw_type(
@@ -355,14 +1160,58 @@ impl Database {
             }
         }
 
-        let mut file = match File::create(path) {
-            Ok(it) => it,
-            Err(_) => return,
-        };
+        match writer.write_all(&bytes) {
+            Ok(it) => Ok(it),
+            Err(_) => Err(SaveError),
+        }
+    }
 
-        match file.write_all(&bytes) {
-            Ok(it) => it,
-            Err(_) => return,
-        };
+    /// Writes database as JSON, an object with the database name and rows of every table by table name
+    /// ## Errors
+    /// Returns a `JsonError` if writer fails
+    /// ## Parameters
+    /// * `writer` - Destination of the JSON text
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::with_name("shop");
+    /// db.create_table("users", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.table_unwrap("users").insert(vec![1_i64.into()]).unwrap();
+    ///
+    /// let mut bytes = vec![];
+    /// db.export_json(&mut bytes).unwrap();
+    /// let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    /// assert_eq!(json, serde_json::json!({ "name": "shop", "tables": { "users": [{ "id": 1 }] } }));
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn export_json(&self, writer: &mut dyn Write) -> Result<(), JsonError> {
+        let tables: serde_json::Map<String, serde_json::Value> = self
+            .tables
+            .iter()
+            .map(|table| {
+                let rows = table.get_all().iter().map(|x| x.to_json_value()).collect();
+                (table.name.clone(), serde_json::Value::Array(rows))
+            })
+            .collect();
+        let json = serde_json::json!({ "name": self.name, "tables": tables });
+        serde_json::to_writer(writer, &json).map_err(|e| JsonError(e.to_string()))
+    }
+
+    /// Returns database as a JSON string, same format as [`Database::export_json`]
+    /// ## Errors
+    /// Returns a `JsonError` if database can not be serialized
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// let db = Database::with_name("shop");
+    /// assert_eq!(db.to_json_string().unwrap(), r#"{"name":"shop","tables":{}}"#);
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn to_json_string(&self) -> Result<String, JsonError> {
+        let mut bytes = vec![];
+        self.export_json(&mut bytes)?;
+        String::from_utf8(bytes).map_err(|e| JsonError(e.to_string()))
     }
 }
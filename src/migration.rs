@@ -0,0 +1,105 @@
+use crate::{table::MigrationError, Database};
+
+/// Migration step, runs against the whole database
+pub type MigrationStep = Box<dyn Fn(&mut Database) -> Result<(), MigrationError> + Send + Sync>;
+
+/// Name of the table applied migrations are recorded in
+pub const MIGRATIONS_TABLE: &str = "__migrations";
+
+/// Schema or data migration with forward and backward steps
+/// ## Example
+/// ```
+/// use safe_en::{
+///     migration::Migration,
+///     table::{TableRow, TypeDefs},
+///     Database,
+/// };
+/// let mut db = Database::new();
+/// db.create_table("users", vec![TableRow::new("name", TypeDefs::String)]).unwrap();
+///
+/// let migration = Migration::new("add_email_column")
+///     .up(|db| {
+///         db.table_unwrap("users")
+///             .ensure_columns(&[("email", TypeDefs::String)])
+///     })
+///     .down(|db| db.table_unwrap("users").retain_columns(&["name"]));
+///
+/// db.apply_migration(migration).unwrap();
+/// assert!(db.table("users").unwrap().has_column("email"));
+/// ```
+pub struct Migration {
+    pub(crate) name: String,
+    pub(crate) up: Option<MigrationStep>,
+    pub(crate) down: Option<MigrationStep>,
+}
+
+impl Migration {
+    /// Creates a migration without steps
+    /// ## Parameters
+    /// * `name` - Unique name of the migration
+    /// ## Example
+    /// ```
+    /// use safe_en::migration::Migration;
+    /// let migration = Migration::new("add_email_column");
+    /// assert_eq!(migration.get_name(), "add_email_column");
+    /// ```
+    pub fn new(name: &str) -> Self {
+        Migration {
+            name: name.to_string(),
+            up: None,
+            down: None,
+        }
+    }
+
+    /// Returns name of the migration
+    /// ## Example
+    /// ```
+    /// use safe_en::migration::Migration;
+    /// let migration = Migration::new("add_email_column");
+    /// assert_eq!(migration.get_name(), "add_email_column");
+    /// ```
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the forward step, applied by [`Database::apply_migration`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{migration::Migration, table::TypeDefs};
+    /// let migration = Migration::new("add_email_column").up(|db| {
+    ///     db.table_unwrap("users")
+    ///         .ensure_columns(&[("email", TypeDefs::String)])
+    /// });
+    /// ```
+    pub fn up<F>(mut self, step: F) -> Self
+    where
+        F: Fn(&mut Database) -> Result<(), MigrationError> + Send + Sync + 'static,
+    {
+        self.up = Some(Box::new(step));
+        self
+    }
+
+    /// Sets the backward step, reverting what the forward step did
+    /// ## Example
+    /// ```
+    /// use safe_en::migration::Migration;
+    /// let migration = Migration::new("add_email_column")
+    ///     .down(|db| db.table_unwrap("users").retain_columns(&["name"]));
+    /// ```
+    pub fn down<F>(mut self, step: F) -> Self
+    where
+        F: Fn(&mut Database) -> Result<(), MigrationError> + Send + Sync + 'static,
+    {
+        self.down = Some(Box::new(step));
+        self
+    }
+}
+
+/// Migration recorded in the `__migrations` table
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedMigration {
+    /// Name of the migration
+    pub name: String,
+    /// Unix timestamp in seconds
+    pub applied_at: u64,
+}
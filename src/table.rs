@@ -1,4 +1,9 @@
-use core::{fmt::Display, ops::Index};
+use crate::utils;
+use core::{cmp::Ordering, fmt::Display, ops::Index};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+};
 
 /// Rust types to be used in the table
 #[derive(Clone, Debug, PartialEq)]
@@ -20,7 +25,59 @@ pub enum TypeDefs {
     /// F64 type
     F64,
     /// Array type
+    ///
+    /// Array length is saved as [`u64`], so an array can hold at most `u64::MAX` elements
     Array(Box<TypeDefs>),
+    /// Fixed size list of values with their own types
+    ///
+    /// A tuple can have at most 255 elements, tables with longer tuple columns can not be created and such rows are not inserted
+    /// ```
+    /// use safe_en::{Database, table::{SafeType, TableRow, TypeDefs, Types}};
+    /// let path = std::env::temp_dir().join("safe_en_tuples.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.create_table("shapes", vec![
+    ///     TableRow::new("center", TypeDefs::Tuple(vec![TypeDefs::F64, TypeDefs::F64])),
+    ///     TableRow::new("corners", TypeDefs::array_of(TypeDefs::Tuple(vec![TypeDefs::I64, TypeDefs::I64]))),
+    /// ]).unwrap();
+    /// let corner = |x: i64, y: i64| SafeType::tuple(vec![x.into(), y.into()]);
+    /// db.table_unwrap("shapes").insert(vec![
+    ///     SafeType::tuple(vec![1.5_f64.into(), 2.5_f64.into()]),
+    ///     SafeType::build(
+    ///         Types::Array(vec![corner(0, 0), corner(3, 5)]),
+    ///         TypeDefs::array_of(TypeDefs::Tuple(vec![TypeDefs::I64, TypeDefs::I64])),
+    ///     ),
+    /// ]).unwrap();
+    /// db.save(path).unwrap();
+    ///
+    /// let mut loaded = Database::load(path).unwrap();
+    /// let row = &loaded.table_unwrap("shapes").get_all()[0];
+    /// assert_eq!(row.get("center").unwrap().value.get_type().tuple_get(1), Some(&Types::F64(2.5)));
+    /// assert_eq!(row.get("corners").unwrap().value.get_type(), Types::Array(vec![corner(0, 0), corner(3, 5)]));
+    ///
+    /// let wide = TypeDefs::Tuple(vec![TypeDefs::I8; 256]);
+    /// assert!(db.create_table("wide", vec![TableRow::new("point", wide)]).is_err());
+    /// ```
+    Tuple(Vec<TypeDefs>),
+    /// Value that can be missing
+    /// ```
+    /// use safe_en::{Database, table::{SafeType, TableRow, TypeDefs, Types}};
+    /// let path = std::env::temp_dir().join("safe_en_options.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("age", TypeDefs::option_of(TypeDefs::I64)),
+    /// ]).unwrap();
+    /// db.table_unwrap("users").insert(vec![SafeType::some(18_i64.into())]).unwrap();
+    /// db.table_unwrap("users").insert(vec![SafeType::none(TypeDefs::I64)]).unwrap();
+    /// db.save(path).unwrap();
+    ///
+    /// let mut loaded = Database::load(path).unwrap();
+    /// let rows = loaded.table_unwrap("users").get_all();
+    /// assert_eq!(rows[0].get("age").unwrap().value, SafeType::some(18_i64.into()));
+    /// assert_eq!(rows[1].get("age").unwrap().value.get_type(), Types::Option(None));
+    /// ```
+    Option(Box<TypeDefs>),
 }
 
 impl Display for TypeDefs {
@@ -35,6 +92,15 @@ impl Display for TypeDefs {
             TypeDefs::F32 => write!(f, "F32"),
             TypeDefs::F64 => write!(f, "F64"),
             TypeDefs::Array(t) => write!(f, "Array({})", t),
+            TypeDefs::Option(t) => write!(f, "Option({})", t),
+            TypeDefs::Tuple(t) => write!(
+                f,
+                "Tuple({})",
+                t.iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -52,6 +118,17 @@ impl TypeDefs {
         TypeDefs::Array(Box::new(t))
     }
 
+    /// Clean way of creating option type
+    /// # Example
+    /// ```
+    /// use safe_en::table::TypeDefs;
+    /// let option_type = TypeDefs::Option(Box::new(TypeDefs::I64));
+    /// assert_eq!(option_type, TypeDefs::option_of(TypeDefs::I64));
+    /// ```
+    pub fn option_of(t: TypeDefs) -> TypeDefs {
+        TypeDefs::Option(Box::new(t))
+    }
+
     /// Get inner type of array
     /// # Example
     /// ```
@@ -66,6 +143,36 @@ impl TypeDefs {
         }
     }
 
+    /// Zero value of the type, used when a column is added to a table with rows
+    ///
+    /// Numbers are 0, strings and arrays are empty, bools are false and chars are `'\0'`
+    /// # Example
+    /// ```
+    /// use safe_en::table::{TypeDefs, Types};
+    /// assert_eq!(TypeDefs::I64.default_value().get_type(), Types::I64(0));
+    /// assert_eq!(TypeDefs::String.default_value().get_type(), Types::String(String::new()));
+    /// assert_eq!(TypeDefs::Bool.default_value().get_type(), Types::Bool(false));
+    /// let array = TypeDefs::array_of(TypeDefs::String).default_value();
+    /// assert_eq!(array.get_type(), Types::Array(vec![]));
+    /// assert_eq!(array.get_type_def(), TypeDefs::array_of(TypeDefs::String));
+    /// ```
+    pub fn default_value(&self) -> SafeType {
+        let rtype = match self {
+            TypeDefs::String => Types::String(String::new()),
+            TypeDefs::Char => Types::Char('\0'),
+            TypeDefs::I8 => Types::I8(0),
+            TypeDefs::I64 => Types::I64(0),
+            TypeDefs::U64 => Types::U64(0),
+            TypeDefs::Bool => Types::Bool(false),
+            TypeDefs::F32 => Types::F32(0.),
+            TypeDefs::F64 => Types::F64(0.),
+            TypeDefs::Array(_) => Types::Array(vec![]),
+            TypeDefs::Tuple(t) => Types::Tuple(t.iter().map(|x| x.default_value()).collect()),
+            TypeDefs::Option(_) => Types::Option(None),
+        };
+        SafeType::build(rtype, self.clone())
+    }
+
     /// Builds a type from base and second layer
     pub(crate) fn from_base_and_second_layer(base: u8, second_layer: u8) -> TypeDefs {
         match base {
@@ -85,18 +192,46 @@ impl TypeDefs {
         }
     }
 
-    /// Returns the id of the type
-    pub(crate) fn get_base_and_second_layer(&self) -> [u8; 2] {
+    /// Returns false if the type has a tuple with more elements than its one byte length in the type id can hold
+    pub(crate) fn fits_type_id(&self) -> bool {
+        match self {
+            TypeDefs::Array(t) | TypeDefs::Option(t) => t.fits_type_id(),
+            TypeDefs::Tuple(t) => t.len() <= u8::MAX as usize && t.iter().all(|x| x.fits_type_id()),
+            _ => true,
+        }
+    }
+
+    /// Returns the id of the type, tuples and options are followed by ids of their inner types
+    pub(crate) fn get_base_and_second_layer(&self) -> Vec<u8> {
         match self {
-            TypeDefs::String => [0, 0],
-            TypeDefs::Char => [1, 0],
-            TypeDefs::I8 => [2, 0],
-            TypeDefs::I64 => [3, 0],
-            TypeDefs::U64 => [4, 0],
-            TypeDefs::Bool => [5, 0],
-            TypeDefs::F32 => [6, 0],
-            TypeDefs::F64 => [7, 0],
-            TypeDefs::Array(t) => [8, t.get_base_and_second_layer()[0]],
+            TypeDefs::String => vec![0, 0],
+            TypeDefs::Char => vec![1, 0],
+            TypeDefs::I8 => vec![2, 0],
+            TypeDefs::I64 => vec![3, 0],
+            TypeDefs::U64 => vec![4, 0],
+            TypeDefs::Bool => vec![5, 0],
+            TypeDefs::F32 => vec![6, 0],
+            TypeDefs::F64 => vec![7, 0],
+            TypeDefs::Array(t) => {
+                let inner = t.get_base_and_second_layer();
+                let mut id = vec![8, inner[0]];
+                if let TypeDefs::Tuple(_) | TypeDefs::Option(_) = **t {
+                    id.extend_from_slice(&inner[1..]);
+                }
+                id
+            }
+            TypeDefs::Option(t) => {
+                let mut id = vec![10];
+                id.extend(t.get_base_and_second_layer());
+                id
+            }
+            TypeDefs::Tuple(t) => {
+                let mut id = vec![9, t.len() as u8];
+                for element in t {
+                    id.extend(element.get_base_and_second_layer());
+                }
+                id
+            }
         }
     }
 }
@@ -122,6 +257,39 @@ pub enum Types {
     F64(f64),
     /// Array type
     Array(Vec<SafeType>),
+    /// Tuple type
+    Tuple(Vec<SafeType>),
+    /// Option type
+    Option(Option<Box<SafeType>>),
+}
+
+/// Values of the same scalar type are ordered, missing Option values come before present ones.
+/// Arrays, tuples and values of different types have no order
+/// ## Example
+/// ```
+/// use safe_en::table::Types;
+/// assert!(Types::I64(18) < Types::I64(30));
+/// assert!(Types::String("a".to_string()) < Types::String("b".to_string()));
+/// assert_eq!(Types::I64(1).partial_cmp(&Types::U64(1)), None);
+/// assert_eq!(Types::Array(vec![]).partial_cmp(&Types::Array(vec![1_i64.into()])), None);
+/// ```
+impl PartialOrd for Types {
+    fn partial_cmp(&self, other: &Types) -> Option<Ordering> {
+        match (self, other) {
+            (Types::String(a), Types::String(b)) => Some(a.cmp(b)),
+            (Types::Char(a), Types::Char(b)) => Some(a.cmp(b)),
+            (Types::I8(a), Types::I8(b)) => Some(a.cmp(b)),
+            (Types::I64(a), Types::I64(b)) => Some(a.cmp(b)),
+            (Types::U64(a), Types::U64(b)) => Some(a.cmp(b)),
+            (Types::Bool(a), Types::Bool(b)) => Some(a.cmp(b)),
+            (Types::F32(a), Types::F32(b)) => a.partial_cmp(b),
+            (Types::F64(a), Types::F64(b)) => a.partial_cmp(b),
+            (Types::Option(Some(a)), Types::Option(Some(b))) => a.rtype.partial_cmp(&b.rtype),
+            (Types::Option(a), Types::Option(b)) => Some(a.is_some().cmp(&b.is_some())),
+            (a, b) if a == b => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Types {
@@ -143,6 +311,16 @@ impl Display for Types {
                     .join(",")
             )
             .fmt(f),
+            Types::Tuple(e) => format!(
+                "({})",
+                e.iter()
+                    .map(|x| format!("{}", x.get_type()))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            )
+            .fmt(f),
+            Types::Option(Some(e)) => format!("Some({})", e.get_type()).fmt(f),
+            Types::Option(None) => "None".fmt(f),
         }
     }
 }
@@ -195,6 +373,50 @@ impl SafeType {
         self.type_id.clone()
     }
 
+    ///Build a tuple, type definition is taken from the elements
+    /// ## Example
+    /// ```
+    /// use safe_en::table::{SafeType, TypeDefs};
+    /// let point = SafeType::tuple(vec![1.5_f64.into(), 2.5_f64.into()]);
+    /// assert_eq!(point.get_type_def(), TypeDefs::Tuple(vec![TypeDefs::F64, TypeDefs::F64]));
+    /// ```
+    pub fn tuple(values: Vec<SafeType>) -> SafeType {
+        let type_id = TypeDefs::Tuple(values.iter().map(|x| x.get_type_def()).collect());
+        SafeType {
+            type_id,
+            rtype: Types::Tuple(values),
+        }
+    }
+
+    ///Build a present option value
+    /// ## Example
+    /// ```
+    /// use safe_en::table::{SafeType, TypeDefs};
+    /// let age = SafeType::some(18_i64.into());
+    /// assert_eq!(age.get_type_def(), TypeDefs::option_of(TypeDefs::I64));
+    /// ```
+    pub fn some(value: SafeType) -> SafeType {
+        SafeType {
+            type_id: TypeDefs::option_of(value.get_type_def()),
+            rtype: Types::Option(Some(Box::new(value))),
+        }
+    }
+
+    ///Build a missing option value of given inner type
+    /// ## Example
+    /// ```
+    /// use safe_en::table::{SafeType, TypeDefs, Types};
+    /// let age = SafeType::none(TypeDefs::I64);
+    /// assert_eq!(age.get_type_def(), TypeDefs::option_of(TypeDefs::I64));
+    /// assert_eq!(age.get_type(), Types::Option(None));
+    /// ```
+    pub fn none(inner: TypeDefs) -> SafeType {
+        SafeType {
+            type_id: TypeDefs::option_of(inner),
+            rtype: Types::Option(None),
+        }
+    }
+
     ///Build a new safe type
     /// ## Returns
     /// [`SafeType`] builded
@@ -210,6 +432,48 @@ impl SafeType {
 }
 
 impl Types {
+    /// Compares values of the same type, values of different types and arrays are equal
+    ///
+    /// Floats are compared with `total_cmp`, so NaN is ordered after every number instead of being equal to all of them
+    pub(crate) fn compare(&self, other: &Types) -> Ordering {
+        match (self, other) {
+            (Types::F32(a), Types::F32(b)) => a.total_cmp(b),
+            (Types::F64(a), Types::F64(b)) => a.total_cmp(b),
+            (Types::Option(Some(a)), Types::Option(Some(b))) => a.rtype.compare(&b.rtype),
+            _ => self.partial_cmp(other).unwrap_or(Ordering::Equal),
+        }
+    }
+
+    /// Convert value to `target` type if no information is lost
+    ///
+    /// I8 widens to I64, F32 and F64, F32 widens to F64. Scalar values convert to their own type unchanged
+    /// ## Returns
+    /// [`Option<Types>`] Converted value, `None` if value can not be widened to `target`
+    /// # Example
+    /// ```
+    /// use safe_en::table::{TypeDefs, Types};
+    /// assert_eq!(Types::I8(3).coerce_to(&TypeDefs::I64), Some(Types::I64(3)));
+    /// assert_eq!(Types::F32(0.5).coerce_to(&TypeDefs::F64), Some(Types::F64(0.5)));
+    /// assert_eq!(Types::I64(3).coerce_to(&TypeDefs::I8), None);
+    /// ```
+    pub fn coerce_to(&self, target: &TypeDefs) -> Option<Types> {
+        match (self, target) {
+            (Types::I8(e), TypeDefs::I64) => Some(Types::I64(*e as i64)),
+            (Types::I8(e), TypeDefs::F32) => Some(Types::F32(*e as f32)),
+            (Types::I8(e), TypeDefs::F64) => Some(Types::F64(*e as f64)),
+            (Types::F32(e), TypeDefs::F64) => Some(Types::F64(*e as f64)),
+            (Types::String(_), TypeDefs::String)
+            | (Types::Char(_), TypeDefs::Char)
+            | (Types::I8(_), TypeDefs::I8)
+            | (Types::I64(_), TypeDefs::I64)
+            | (Types::U64(_), TypeDefs::U64)
+            | (Types::Bool(_), TypeDefs::Bool)
+            | (Types::F32(_), TypeDefs::F32)
+            | (Types::F64(_), TypeDefs::F64) => Some(self.clone()),
+            _ => None,
+        }
+    }
+
     /// Returns true if type is string
     /// # Example
     /// ```
@@ -336,6 +600,66 @@ impl Types {
         }
     }
 
+    /// Returns true if type is tuple
+    /// # Example
+    /// ```
+    /// use safe_en::table::{Types};
+    /// let t = Types::Tuple(vec![1.5_f64.into(), 2.5_f64.into()]);
+    /// assert_eq!(t.is_tuple(), true);
+    /// ```
+    pub fn is_tuple(&self) -> bool {
+        matches!(self, Types::Tuple(_))
+    }
+
+    /// Get element of a tuple
+    /// # Example
+    /// ```
+    /// use safe_en::table::{Types};
+    /// let point = Types::Tuple(vec![1.5_f64.into(), 2.5_f64.into()]);
+    /// assert_eq!(point.tuple_get(1), Some(&Types::F64(2.5)));
+    /// assert_eq!(point.tuple_get(2), None);
+    /// assert_eq!(Types::F64(1.5).tuple_get(0), None);
+    /// ```
+    pub fn tuple_get(&self, index: usize) -> Option<&Types> {
+        match self {
+            Types::Tuple(e) => e.get(index).map(|x| &x.rtype),
+            _ => None,
+        }
+    }
+
+    /// Convert to its natural JSON representation
+    ///
+    /// Chars become strings, non finite floats become `null`
+    /// ## Example
+    /// ```
+    /// use safe_en::table::Types;
+    /// assert_eq!(Types::Char('a').to_json_value(), serde_json::json!("a"));
+    /// assert_eq!(Types::F64(1.5).to_json_value(), serde_json::json!(1.5));
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        match self {
+            Types::String(e) => serde_json::Value::String(e.clone()),
+            Types::Char(e) => serde_json::Value::String(e.to_string()),
+            Types::I8(e) => serde_json::Value::from(*e),
+            Types::I64(e) => serde_json::Value::from(*e),
+            Types::U64(e) => serde_json::Value::from(*e),
+            Types::Bool(e) => serde_json::Value::Bool(*e),
+            Types::F32(e) => serde_json::Number::from_f64(*e as f64)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Types::F64(e) => serde_json::Number::from_f64(*e)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Types::Array(e) | Types::Tuple(e) => {
+                serde_json::Value::Array(e.iter().map(|x| x.rtype.to_json_value()).collect())
+            }
+            Types::Option(Some(e)) => e.rtype.to_json_value(),
+            Types::Option(None) => serde_json::Value::Null,
+        }
+    }
+
     /// Convert to string
     pub fn to_string(&self) -> String {
         match self {
@@ -856,6 +1180,25 @@ impl From<Types> for Vec<f64> {
     }
 }
 
+/// Type mismatch error
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    /// Expected type
+    pub expected: TypeDefs,
+    /// Given type
+    pub got: TypeDefs,
+}
+
+impl Display for TypeMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Type mismatch, expected {}, got {}",
+            self.expected, self.got
+        )
+    }
+}
+
 /// Row of table
 /// Key is header of the table
 /// Value is the value of the row
@@ -875,123 +1218,636 @@ impl Entry {
     {
         self.value.get()
     }
+
+    /// Set the value of the entry, keeping its type
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<TypeMismatch>`] If the new value has a different type
+    /// ## Example
+    /// ```
+    /// use safe_en::table::{Entry, TypeDefs, TypeMismatch};
+    /// let mut entry = Entry {
+    ///     key: "name".to_string(),
+    ///     value: "John".into(),
+    /// };
+    /// entry.set_value("Jane".into()).unwrap();
+    /// assert_eq!(entry.get::<String>(), "Jane");
+    /// assert_eq!(
+    ///     entry.set_value(12_i64.into()),
+    ///     Err(TypeMismatch { expected: TypeDefs::String, got: TypeDefs::I64 })
+    /// );
+    /// ```
+    pub fn set_value(&mut self, new_value: SafeType) -> Result<(), TypeMismatch> {
+        if new_value.type_id != self.value.type_id {
+            return Err(TypeMismatch {
+                expected: self.value.get_type_def(),
+                got: new_value.get_type_def(),
+            });
+        }
+        self.value = new_value;
+        Ok(())
+    }
 }
 
-/// Table
-#[derive(Clone, Debug)]
-pub struct Table {
-    pub(crate) name: String,
-    pub(crate) headers: Vec<TableRow>,
-    pub(crate) columns: Vec<Vec<SafeType>>,
+/// Schema migration error
+#[derive(Clone, Debug, PartialEq)]
+pub enum MigrationError {
+    /// Column exists with a different type
+    TypeConflict {
+        /// Name of the column
+        column: String,
+        /// Required type
+        expected: TypeDefs,
+        /// Type of the existing column
+        got: TypeDefs,
+    },
+    /// Column does not exist in table
+    ColumnNotFound(String),
+    /// Migration with the same name is already applied
+    AlreadyApplied(String),
+    /// Migration step failed
+    Failed(String),
 }
 
-impl Display for Table {
+impl Display for MigrationError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut lines = String::new();
-        let max_row_lengths = if self.columns.len() == 0 {
-            self.headers
-                .iter()
-                .map(|x| format!(" {} ", x.key).len())
-                .collect()
-        } else {
-            let mut rows: Vec<usize> = self
-                .headers
-                .iter()
-                .map(|x| format!(" {} ", x.key).len())
-                .collect();
-            for row in self.columns.iter() {
-                for (index, _) in self.headers.iter().enumerate() {
-                    let row_len = format!("{}", row[index].get_type()).len() + 2;
-                    if rows[index] < row_len {
-                        rows[index] = row_len;
-                    }
-                }
+        match self {
+            MigrationError::TypeConflict {
+                column,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Column '{}' type conflict, expected {}, got {}",
+                column, expected, got
+            ),
+            MigrationError::ColumnNotFound(column) => {
+                write!(f, "Could not find column '{}' in table", column)
             }
-            rows
-        };
-
-        let mut header_line = String::from("|");
+            MigrationError::AlreadyApplied(name) => {
+                write!(f, "Migration '{}' is already applied", name)
+            }
+            MigrationError::Failed(message) => write!(f, "Migration failed: {}", message),
+        }
+    }
+}
 
-        for (index, header) in self.headers.iter().enumerate() {
-            header_line += &format!(" {} ", header.key);
+/// Error returned when copying rows between tables with different schemas
+#[derive(Clone, Debug, PartialEq)]
+pub enum CopyError {
+    /// Tables have different number of columns
+    ColumnCountMismatch {
+        /// Column count of target table
+        expected: usize,
+        /// Column count of source table
+        got: usize,
+    },
+    /// Column at the same position has a different name
+    ColumnNameMismatch {
+        /// Column name in target table
+        expected: String,
+        /// Column name in source table
+        got: String,
+    },
+    /// Column has a different type
+    SchemaMismatch {
+        /// Name of the column
+        column: String,
+        /// Column type in target table
+        expected: TypeDefs,
+        /// Column type in source table
+        got: TypeDefs,
+    },
+    /// Copied row repeats a value in a unique column of target table, nothing is copied
+    UniqueViolation {
+        /// Index of the target row repeating the value
+        row: usize,
+    },
+}
 
-            for _ in 0..max_row_lengths[index] - format!(" {} ", header.key).len() {
-                header_line += " ";
+impl Display for CopyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CopyError::ColumnCountMismatch { expected, got } => write!(
+                f,
+                "Column count mismatch, expected {}, got {}",
+                expected, got
+            ),
+            CopyError::ColumnNameMismatch { expected, got } => write!(
+                f,
+                "Column name mismatch, expected '{}', got '{}'",
+                expected, got
+            ),
+            CopyError::SchemaMismatch {
+                column,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Column '{}' type mismatch, expected {}, got {}",
+                column, expected, got
+            ),
+            CopyError::UniqueViolation { row } => {
+                write!(f, "Unique constraint violated on row {}", row)
             }
-            header_line += " |";
         }
+    }
+}
 
-        let mut spliter = String::new();
-        for i in header_line.split("") {
-            spliter += if i == "|" {
-                "|"
-            } else if i == " " {
-                " "
-            } else if i != "" {
-                "-"
-            } else {
-                ""
-            };
+/// Error returned by [`Table::bulk_set_column`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BulkSetError {
+    /// Column does not exist
+    ColumnNotFound(String),
+    /// Number of values is not the number of rows
+    LengthMismatch {
+        /// Row count of the table
+        expected: usize,
+        /// Number of values given
+        got: usize,
+    },
+    /// Value has a different type than the column
+    TypeMismatch {
+        /// Index of the row
+        row: usize,
+        /// Column type
+        expected: TypeDefs,
+        /// Value type
+        got: TypeDefs,
+    },
+    /// Value repeats in a unique column
+    UniqueViolation {
+        /// Index of the row repeating the value
+        row: usize,
+    },
+}
+
+impl Display for BulkSetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BulkSetError::ColumnNotFound(column) => {
+                write!(f, "Could not find key '{}' in table", column)
+            }
+            BulkSetError::LengthMismatch { expected, got } => write!(
+                f,
+                "Length mismatch, expected {} values, got {}",
+                expected, got
+            ),
+            BulkSetError::TypeMismatch { row, expected, got } => write!(
+                f,
+                "Type mismatch, expected {}, got {} on row {}",
+                expected, got, row
+            ),
+            BulkSetError::UniqueViolation { row } => {
+                write!(f, "Unique constraint violated on row {}", row)
+            }
         }
-        lines += &format!("{}\n{}", header_line, spliter);
+    }
+}
 
-        for column in &self.columns {
-            let mut row_line = String::from("|");
+/// Error returned by [`Table::zip_with`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ZipError {
+    /// Tables have different row counts
+    LengthMismatch {
+        /// Row count of the left table
+        left: usize,
+        /// Row count of the right table
+        right: usize,
+    },
+    /// Merged row does not have the column count or types of the first merged row
+    RowMismatch {
+        /// Index of the row
+        row: usize,
+    },
+}
 
-            for (index, _) in self.headers.iter().enumerate() {
-                let mut row_len = String::new();
-                let rtype = format!(" {}", column[index].get_type());
-                for i in 0..max_row_lengths[index] {
-                    if i < rtype.len() {
-                        row_len += &rtype.chars().nth(i).unwrap_or(' ').to_string();
-                    } else {
-                        row_len += " ";
-                    }
-                }
-                row_line += &format!("{} |", row_len);
+impl Display for ZipError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ZipError::LengthMismatch { left, right } => write!(
+                f,
+                "Row count mismatch, left table has {}, right table has {}",
+                left, right
+            ),
+            ZipError::RowMismatch { row } => {
+                write!(f, "Merged row {} does not match schema of first row", row)
             }
-            lines += &format!("\n{}", row_line);
         }
-        write!(f, "{}", lines)
     }
 }
 
-/// A column in a table
-///
-/// ```
-/// /// | name | age |
-/// /// | --- | --- |
-/// /// | John | 12 |
-/// use safe_en::table::{Types, Entry, Entries, SafeType, TypeDefs};
-/// Entries {
-///    entries: vec![
-///        Entry {
-///          key: "name".to_string(),
-///          value: SafeType { type_id: TypeDefs::String, rtype: Types::String("John".to_string()) },
-///        },
-///        Entry {
-///          key: "age".to_string(),
-///          value: SafeType { type_id: TypeDefs::I64, rtype: Types::I64(12) },
-///        },
-///     ],
-/// };
-/// ```
-#[derive(Clone)]
-pub struct Entries {
-    /// Rows of the table with key and value
-    pub entries: Vec<Entry>,
+/// Error returned by set operations between tables
+#[derive(Clone, Debug, PartialEq)]
+pub enum SetOpError {
+    /// Tables do not have the same column names and types in the same order
+    SchemaMismatch,
 }
 
-impl Display for Entries {
+impl Display for SetOpError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut header_line = String::from("| ");
-        let mut spacer_line = String::from("| ");
-        let mut val_line = String::from("| ");
+        match self {
+            SetOpError::SchemaMismatch => write!(f, "Tables have different schemas"),
+        }
+    }
+}
 
-        let longest_val_len = self
-            .entries
-            .iter()
-            .map(|x| format!("{}", x.value.get_type()).len())
+/// Error returned by [`Entries::merge`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeError {
+    /// Key exists in both rows
+    DuplicateKey(String),
+}
+
+impl Display for MergeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MergeError::DuplicateKey(key) => write!(f, "Key '{}' exists in both rows", key),
+        }
+    }
+}
+
+/// Columns with at most this many distinct values report their distinct count in [`ColumnSummary`]
+const LOW_CARDINALITY_LIMIT: usize = 16;
+
+/// Summary of a column, part of [`TableSummary`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnSummary {
+    /// Column name
+    pub name: String,
+    /// Column type
+    pub rtype: TypeDefs,
+    /// Number of distinct values, only set for columns with at most 16 distinct values
+    pub distinct_count: Option<usize>,
+    /// Smallest value of a numeric column
+    pub min: Option<f64>,
+    /// Largest value of a numeric column
+    pub max: Option<f64>,
+    /// Arithmetic mean of a numeric column
+    pub mean: Option<f64>,
+    /// Most frequent value of a string, char or bool column, first seen value wins ties
+    pub mode: Option<Types>,
+}
+
+/// Summary of a table, returned by [`Table::summarize`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableSummary {
+    /// Table name
+    pub name: String,
+    /// Number of columns
+    pub column_count: usize,
+    /// Number of rows
+    pub row_count: usize,
+    /// Summary of each column, in column order
+    pub columns: Vec<ColumnSummary>,
+}
+
+impl Display for TableSummary {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        let mut lines = vec![vec![
+            "column".to_string(),
+            "type".to_string(),
+            "distinct".to_string(),
+            "min".to_string(),
+            "max".to_string(),
+            "mean".to_string(),
+            "mode".to_string(),
+        ]];
+        for column in &self.columns {
+            lines.push(vec![
+                column.name.clone(),
+                column.rtype.to_string(),
+                optional(column.distinct_count.map(|x| x.to_string())),
+                optional(column.min.map(|x| x.to_string())),
+                optional(column.max.map(|x| x.to_string())),
+                optional(column.mean.map(|x| x.to_string())),
+                optional(column.mode.as_ref().map(|x| x.to_string())),
+            ]);
+        }
+        let widths: Vec<usize> = (0..lines[0].len())
+            .map(|i| {
+                lines
+                    .iter()
+                    .map(|x| x[i].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        writeln!(
+            f,
+            "{}: {} columns, {} rows",
+            self.name, self.column_count, self.row_count
+        )?;
+        for (index, line) in lines.iter().enumerate() {
+            let cells: Vec<String> = line
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect();
+            write!(f, "| {} |", cells.join(" | "))?;
+            if index == 0 {
+                let spliter: Vec<String> = widths.iter().map(|x| "-".repeat(*x)).collect();
+                write!(f, "\n| {} |", spliter.join(" | "))?;
+            }
+            if index + 1 < lines.len() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Data quality profile of a column, part of [`DataProfile`]
+///
+/// `None` options, empty strings and empty arrays are counted as missing
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnProfile {
+    /// Column name
+    pub name: String,
+    /// Fraction of rows with an empty value
+    pub null_rate: f64,
+    /// Fraction of rows holding a distinct value
+    pub unique_rate: f64,
+    /// Most frequent value, first seen value wins ties
+    pub most_common_value: Option<Types>,
+    /// Least frequent value, first seen value wins ties
+    pub least_common_value: Option<Types>,
+    /// Fraction of rows with a non empty value
+    pub completeness: f64,
+}
+
+/// Data quality profile of a table, returned by [`Table::profile`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataProfile {
+    /// Number of rows
+    pub total_rows: usize,
+    /// Profile of each column, in column order
+    pub per_column: Vec<ColumnProfile>,
+}
+
+/// How [`Table::fill_missing`] picks values for missing cells
+#[derive(Clone, Debug, PartialEq)]
+pub enum FillStrategy {
+    /// Previous present value, cells before the first present value stay missing
+    Forward,
+    /// Next present value, cells after the last present value stay missing
+    Backward,
+    /// Mean of present values, rounded for integer columns
+    Mean,
+    /// Median of present values, rounded for integer columns
+    Median,
+    /// Given value, must have the inner type of the column
+    Constant(SafeType),
+}
+
+/// Aggregate function used by [`Table::window_aggregate`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AggFunc {
+    /// Sum of values
+    Sum,
+    /// Arithmetic mean of values
+    Mean,
+    /// Smallest value
+    Min,
+    /// Largest value
+    Max,
+    /// Number of values
+    Count,
+}
+
+/// Error returned by statistics over table columns
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatsError {
+    /// Column does not exist in table
+    ColumnNotFound(String),
+    /// Column is not a number type
+    NotNumeric(String),
+    /// Table has too few rows for the statistic
+    InsufficientData,
+}
+
+impl Display for StatsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StatsError::ColumnNotFound(column) => {
+                write!(f, "Could not find column '{}' in table", column)
+            }
+            StatsError::NotNumeric(column) => write!(f, "Column '{}' is not numeric", column),
+            StatsError::InsufficientData => write!(f, "Not enough rows"),
+        }
+    }
+}
+
+/// Error returned by [`Table::binary_search`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchError {
+    /// Column does not exist in table
+    ColumnNotFound(String),
+    /// No row has the value
+    NotFound,
+    /// Column was found out of ascending order during the search
+    Unsorted,
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SearchError::ColumnNotFound(column) => {
+                write!(f, "Could not find column '{}' in table", column)
+            }
+            SearchError::NotFound => write!(f, "Value not found"),
+            SearchError::Unsorted => write!(f, "Column is not sorted"),
+        }
+    }
+}
+
+/// Sums of deviation products, see [`Table::covariance`]
+struct DeviationSums {
+    n: f64,
+    ab: f64,
+    aa: f64,
+    bb: f64,
+}
+
+/// Sort direction used by [`Table::rank`] and [`Table::stable_sort`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    /// Smallest value first
+    Ascending,
+    /// Largest value first
+    Descending,
+}
+
+/// Kind of change that produced a [`TableEvent`]
+#[cfg(feature = "events")]
+#[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventType {
+    /// Row inserted
+    Insert,
+    /// Row updated
+    Update,
+    /// Row deleted
+    Delete,
+}
+
+/// Change event sent to [`Table::subscribe`] receivers
+#[cfg(feature = "events")]
+#[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+#[derive(Clone)]
+pub struct TableEvent {
+    /// Kind of change
+    pub event_type: EventType,
+    /// Affected row, for updates this is the row after the change
+    pub row: Option<Entries>,
+}
+
+/// Table
+#[derive(Debug)]
+pub struct Table {
+    pub(crate) name: String,
+    pub(crate) headers: Vec<TableRow>,
+    pub(crate) columns: Vec<Vec<SafeType>>,
+    #[cfg(feature = "events")]
+    pub(crate) subscribers: Vec<std::sync::mpsc::Sender<TableEvent>>,
+    pub(crate) row_cache: Option<utils::LruCache<usize, Entries>>,
+    pub(crate) column_metadata: HashMap<String, HashMap<String, String>>,
+    pub(crate) unique_columns: Vec<String>,
+    pub(crate) column_defaults: HashMap<String, SafeType>,
+    pub(crate) lru_indexes: HashMap<String, utils::LruCache<Vec<u8>, Vec<usize>>>,
+    pub(crate) bloom_indexes: utils::BloomIndexes,
+    pub(crate) unique_index: utils::UniqueIndex,
+}
+
+/// Clones start without subscribers, changes made to the copy are not reported to receivers of the original
+impl Clone for Table {
+    fn clone(&self) -> Self {
+        Table {
+            name: self.name.clone(),
+            headers: self.headers.clone(),
+            columns: self.columns.clone(),
+            #[cfg(feature = "events")]
+            subscribers: vec![],
+            row_cache: self.row_cache.clone(),
+            column_metadata: self.column_metadata.clone(),
+            unique_columns: self.unique_columns.clone(),
+            column_defaults: self.column_defaults.clone(),
+            lru_indexes: self.lru_indexes.clone(),
+            bloom_indexes: self.bloom_indexes.clone(),
+            unique_index: self.unique_index.clone(),
+        }
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut lines = String::new();
+        let max_row_lengths = if self.columns.len() == 0 {
+            self.headers
+                .iter()
+                .map(|x| format!(" {} ", x.key).len())
+                .collect()
+        } else {
+            let mut rows: Vec<usize> = self
+                .headers
+                .iter()
+                .map(|x| format!(" {} ", x.key).len())
+                .collect();
+            for row in self.columns.iter() {
+                for (index, _) in self.headers.iter().enumerate() {
+                    let row_len = format!("{}", row[index].get_type()).len() + 2;
+                    if rows[index] < row_len {
+                        rows[index] = row_len;
+                    }
+                }
+            }
+            rows
+        };
+
+        let mut header_line = String::from("|");
+
+        for (index, header) in self.headers.iter().enumerate() {
+            header_line += &format!(" {} ", header.key);
+
+            for _ in 0..max_row_lengths[index] - format!(" {} ", header.key).len() {
+                header_line += " ";
+            }
+            header_line += " |";
+        }
+
+        let mut spliter = String::new();
+        for i in header_line.split("") {
+            spliter += if i == "|" {
+                "|"
+            } else if i == " " {
+                " "
+            } else if i != "" {
+                "-"
+            } else {
+                ""
+            };
+        }
+        lines += &format!("{}\n{}", header_line, spliter);
+
+        for column in &self.columns {
+            let mut row_line = String::from("|");
+
+            for (index, _) in self.headers.iter().enumerate() {
+                let mut row_len = String::new();
+                let rtype = format!(" {}", column[index].get_type());
+                for i in 0..max_row_lengths[index] {
+                    if i < rtype.len() {
+                        row_len += &rtype.chars().nth(i).unwrap_or(' ').to_string();
+                    } else {
+                        row_len += " ";
+                    }
+                }
+                row_line += &format!("{} |", row_len);
+            }
+            lines += &format!("\n{}", row_line);
+        }
+        write!(f, "{}", lines)
+    }
+}
+
+/// A column in a table
+///
+/// ```
+/// /// | name | age |
+/// /// | --- | --- |
+/// /// | John | 12 |
+/// use safe_en::table::{Types, Entry, Entries, SafeType, TypeDefs};
+/// Entries {
+///    entries: vec![
+///        Entry {
+///          key: "name".to_string(),
+///          value: SafeType { type_id: TypeDefs::String, rtype: Types::String("John".to_string()) },
+///        },
+///        Entry {
+///          key: "age".to_string(),
+///          value: SafeType { type_id: TypeDefs::I64, rtype: Types::I64(12) },
+///        },
+///     ],
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct Entries {
+    /// Rows of the table with key and value
+    pub entries: Vec<Entry>,
+}
+
+impl Display for Entries {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut header_line = String::from("| ");
+        let mut spacer_line = String::from("| ");
+        let mut val_line = String::from("| ");
+
+        let longest_val_len = self
+            .entries
+            .iter()
+            .map(|x| format!("{}", x.value.get_type()).len())
             .max()
             .unwrap();
         let longest_key_len = self
@@ -1045,9 +1901,77 @@ impl Index<usize> for Entries {
     }
 }
 
+/// Options for [`RowQuery::like_with_options`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LikeOptions {
+    /// Match letters case sensitively, defaults to `true`
+    pub case_sensitive: bool,
+}
+
+impl Default for LikeOptions {
+    fn default() -> Self {
+        LikeOptions {
+            case_sensitive: true,
+        }
+    }
+}
+
+/// Invalid regex pattern error
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[derive(Debug, Clone)]
+pub struct RegexError(pub regex::Error);
+
+#[cfg(feature = "regex")]
+impl Display for RegexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Invalid regex pattern: {}", self.0)
+    }
+}
+
+/// Row changes between two tables, returned by [`Table::diff`] and applied with [`Table::apply_diff`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableDiff {
+    /// Indexes of rows to remove
+    pub removed_rows: Vec<usize>,
+    /// Rows to append
+    pub added_rows: Vec<Vec<SafeType>>,
+    /// Indexes of rows to replace and their new values
+    pub modified_rows: Vec<(usize, Vec<SafeType>)>,
+}
+
+/// A single difference between two [`Entries`], returned by [`Entries::diff`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntryDiff {
+    /// Key exists in both entries with different values
+    Changed {
+        /// Key of the entry
+        key: String,
+        /// Value in the original entries
+        old: Types,
+        /// Value in the compared entries
+        new: Types,
+    },
+    /// Key only exists in the compared entries
+    Added {
+        /// Key of the entry
+        key: String,
+        /// Value in the compared entries
+        new: Types,
+    },
+    /// Key only exists in the original entries
+    Removed {
+        /// Key of the entry
+        key: String,
+        /// Value in the original entries
+        old: Types,
+    },
+}
+
 /// Row query is a query tool for filtering rows
 pub struct RowQuery {
     entry: Option<Entry>,
+    strict: bool,
 }
 
 impl RowQuery {
@@ -1087,7 +2011,29 @@ impl RowQuery {
         }
     }
 
+    /// Set whether [`RowQuery::is`] and comparisons require the exact type of the entry
+    ///
+    /// Queries are strict by default, with `strict_match(false)` values are widened with [`Types::coerce_to`] when types differ
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![12_i64.into()]).unwrap();
+    /// let row = &db.table("users").unwrap().get_all()[0];
+    /// assert_eq!(row.row("age").is(12_i8), false);
+    /// assert_eq!(row.row("age").strict_match(false).is(12_i8), true);
+    /// ```
+    pub fn strict_match(mut self, strict: bool) -> RowQuery {
+        self.strict = strict;
+        self
+    }
+
     /// Check if entry is the equvalent of the given value
+    ///
+    /// If [`RowQuery::strict_match`] is turned off, values of different types are equal if the narrower one widens to the other
     /// ## Example
     /// ```
     /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
@@ -1108,8 +2054,14 @@ impl RowQuery {
         T: Into<SafeType> + PartialEq,
     {
         if let Some(entry) = &self.entry {
-            let f = Into::into(key);
-            f == entry.value
+            let f: SafeType = Into::into(key);
+            if f == entry.value {
+                return true;
+            }
+            !self.strict
+                && (f.rtype.coerce_to(&entry.value.get_type_def()).as_ref()
+                    == Some(&entry.value.rtype)
+                    || entry.value.rtype.coerce_to(&f.get_type_def()).as_ref() == Some(&f.rtype))
             /*
             match &f {
                 Types::String(_) => entry.value.get_type_name() == TypeDefs::String && entry.value == f,
@@ -1133,56 +2085,415 @@ impl RowQuery {
         }
     }
 
-    /// Check if entry is the equvalent of the given type
+    /// Order of the entry against `value`, widening like [`RowQuery::is`] when strict match is off
+    fn compare_with(&self, value: SafeType) -> Option<Ordering> {
+        let entry = self.entry.as_ref()?;
+        if let Some(ordering) = entry.value.rtype.partial_cmp(&value.rtype) {
+            return Some(ordering);
+        }
+        if self.strict {
+            return None;
+        }
+        if let Some(widened) = value.rtype.coerce_to(&entry.value.get_type_def()) {
+            return entry.value.rtype.partial_cmp(&widened);
+        }
+        entry
+            .value
+            .rtype
+            .coerce_to(&value.get_type_def())
+            .and_then(|widened| widened.partial_cmp(&value.rtype))
+    }
+
+    /// Check if entry is greater than the given value
+    ///
+    /// Works on numeric, String and Char entries, returns false if the entry does not exist or types can not be compared
     /// ## Example
     /// ```
-    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
     ///    TableRow::new("name", TypeDefs::String),
     ///    TableRow::new("age", TypeDefs::I64),
     /// ]);
-    /// db.table("users").unwrap().insert(vec![
-    ///     "John".into(),
-    ///     12_i64.into()
-    /// ]);
-    /// let first_column = &db.table("users").unwrap().get_where(|x| x.row("name").is("John".to_string()))[0];
-    /// assert_eq!(first_column.row("name").is_it(TypeDefs::String), true);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 12_i64.into()]).unwrap();
+    /// table.insert(vec!["Jane".into(), 30_i64.into()]).unwrap();
+    /// assert_eq!(table.get_where(|x| x.row("age").gt(12_i64)).len(), 1);
+    /// assert_eq!(table.get_where(|x| x.row("age").gt("12")).len(), 0);
     /// ```
-    pub fn is_it(&self, key: TypeDefs) -> bool {
-        if let Some(entry) = &self.entry {
-            key == entry.value.get_type_def()
-        } else {
-            false
-        }
+    pub fn gt<T: Into<SafeType>>(&self, value: T) -> bool {
+        self.compare_with(value.into()) == Some(Ordering::Greater)
     }
 
-    /// Get the value of the entry
+    /// Check if entry is less than the given value
+    ///
+    /// Works on numeric, String and Char entries, returns false if the entry does not exist or types can not be compared
     /// ## Example
     /// ```
-    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
     ///    TableRow::new("name", TypeDefs::String),
-    ///    TableRow::new("age", TypeDefs::I64),
-    /// ]);
-    /// db.table("users").unwrap().insert(vec![
-    ///     "John".into(),
-    ///     12_i64.into()
     /// ]);
-    /// let first_column = &db.table("users").unwrap().get_where(|x| x.row("name").is("John".to_string()))[0];
-    /// assert_eq!(first_column.row("name").get_value(), Some("John".to_string()));
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into()]).unwrap();
+    /// table.insert(vec!["Mehmet".into()]).unwrap();
+    /// assert_eq!(table.get_where(|x| x.row("name").lt("B")).len(), 1);
     /// ```
-    pub fn get_value<T>(&self) -> Option<T>
-    where
-        T: From<Types>,
-    {
+    pub fn lt<T: Into<SafeType>>(&self, value: T) -> bool {
+        self.compare_with(value.into()) == Some(Ordering::Less)
+    }
+
+    /// Check if entry is greater than or equal to the given value
+    ///
+    /// Works on numeric, String and Char entries, returns false if the entry does not exist or types can not be compared
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for age in [12_i64, 18, 30, 45] {
+    ///     table.insert(vec![age.into()]).unwrap();
+    /// }
+    /// assert_eq!(table.get_where(|x| x.row("age").gte(18_i64) && x.row("age").lte(30_i64)).len(), 2);
+    /// // Values of another type are only compared once strict match is off
+    /// assert_eq!(table.get_where(|x| x.row("age").gte(18_i8)).len(), 0);
+    /// assert_eq!(table.get_where(|x| x.row("age").strict_match(false).gte(18_i8)).len(), 3);
+    /// ```
+    pub fn gte<T: Into<SafeType>>(&self, value: T) -> bool {
+        matches!(
+            self.compare_with(value.into()),
+            Some(Ordering::Greater | Ordering::Equal)
+        )
+    }
+
+    /// Check if entry is less than or equal to the given value
+    ///
+    /// Works on numeric, String and Char entries, returns false if the entry does not exist or types can not be compared
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("prices", vec![
+    ///    TableRow::new("price", TypeDefs::F64),
+    /// ]);
+    /// let table = db.table("prices").unwrap();
+    /// table.insert(vec![9.5_f64.into()]).unwrap();
+    /// table.insert(vec![10.0_f64.into()]).unwrap();
+    /// table.insert(vec![10.5_f64.into()]).unwrap();
+    /// assert_eq!(table.get_where(|x| x.row("price").lte(10.0_f64)).len(), 2);
+    /// assert_eq!(table.get_where(|x| x.row("missing").lte(10.0_f64)).len(), 0);
+    /// ```
+    pub fn lte<T: Into<SafeType>>(&self, value: T) -> bool {
+        matches!(
+            self.compare_with(value.into()),
+            Some(Ordering::Less | Ordering::Equal)
+        )
+    }
+
+    /// Check if entry is between `low` and `high`, both inclusive
+    ///
+    /// Both bounds must have the type of the entry, returns false if the entry does not exist or a type differs
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for age in [12_i64, 18, 30, 65, 70] {
+    ///     table.insert(vec![age.into()]).unwrap();
+    /// }
+    /// assert_eq!(table.get_where(|x| x.row("age").between(18_i64, 65_i64)).len(), 3);
+    /// assert_eq!(table.get_where(|x| x.row("age").between(18_u64, 65_u64)).len(), 0);
+    /// ```
+    pub fn between<T: Into<SafeType> + Clone>(&self, low: T, high: T) -> bool {
+        let entry_type = match &self.entry {
+            Some(entry) => entry.value.get_type_def(),
+            None => return false,
+        };
+        let (low, high): (SafeType, SafeType) = (low.into(), high.into());
+        if low.get_type_def() != entry_type || high.get_type_def() != entry_type {
+            return false;
+        }
+        matches!(
+            self.compare_with(low),
+            Some(Ordering::Greater | Ordering::Equal)
+        ) && matches!(
+            self.compare_with(high),
+            Some(Ordering::Less | Ordering::Equal)
+        )
+    }
+
+    /// Check if entry is the equvalent of the given type
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![
+    ///     "John".into(),
+    ///     12_i64.into()
+    /// ]);
+    /// let first_column = &db.table("users").unwrap().get_where(|x| x.row("name").is("John".to_string()))[0];
+    /// assert_eq!(first_column.row("name").is_it(TypeDefs::String), true);
+    /// ```
+    pub fn is_it(&self, key: TypeDefs) -> bool {
+        if let Some(entry) = &self.entry {
+            key == entry.value.get_type_def()
+        } else {
+            false
+        }
+    }
+
+    /// Get the value of the entry
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![
+    ///     "John".into(),
+    ///     12_i64.into()
+    /// ]);
+    /// let first_column = &db.table("users").unwrap().get_where(|x| x.row("name").is("John".to_string()))[0];
+    /// assert_eq!(first_column.row("name").get_value(), Some("John".to_string()));
+    /// ```
+    pub fn get_value<T>(&self) -> Option<T>
+    where
+        T: From<Types>,
+    {
         if let Some(entry) = &self.entry {
             Some(entry.value.get())
         } else {
             None
         }
     }
+
+    /// Value of a string entry, `None` if the entry does not exist or is not a string
+    fn string_value(&self) -> Option<&str> {
+        match &self.entry.as_ref()?.value.rtype {
+            Types::String(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Check if a string entry contains the given text
+    ///
+    /// Returns false if the entry does not exist or is not a string
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec!["john@example.com".into()]);
+    /// db.table("users").unwrap().insert(vec!["jane@mail.com".into()]);
+    /// let found = db.table("users").unwrap().get_where(|x| x.row("email").contains_str("@example.com"));
+    /// assert_eq!(found.len(), 1);
+    /// ```
+    pub fn contains_str(&self, needle: &str) -> bool {
+        self.string_value().is_some_and(|x| x.contains(needle))
+    }
+
+    /// Check if a string entry starts with the given text
+    ///
+    /// Returns false if the entry does not exist or is not a string
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("files", vec![
+    ///    TableRow::new("path", TypeDefs::String),
+    ///    TableRow::new("size", TypeDefs::I64),
+    /// ]);
+    /// db.table("files").unwrap().insert(vec!["/tmp/a.txt".into(), 10_i64.into()]);
+    /// db.table("files").unwrap().insert(vec!["/home/b.txt".into(), 20_i64.into()]);
+    /// let found = db.table("files").unwrap().get_where(|x| x.row("path").starts_with_str("/tmp/"));
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].row("size").starts_with_str("1"), false);
+    /// ```
+    pub fn starts_with_str(&self, prefix: &str) -> bool {
+        self.string_value().is_some_and(|x| x.starts_with(prefix))
+    }
+
+    /// Check if a string entry ends with the given text
+    ///
+    /// Returns false if the entry does not exist or is not a string
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("files", vec![
+    ///    TableRow::new("path", TypeDefs::String),
+    /// ]);
+    /// db.table("files").unwrap().insert(vec!["/tmp/a.txt".into()]);
+    /// db.table("files").unwrap().insert(vec!["/tmp/b.png".into()]);
+    /// let found = db.table("files").unwrap().get_where(|x| x.row("path").ends_with_str(".txt"));
+    /// assert_eq!(found.len(), 1);
+    /// ```
+    pub fn ends_with_str(&self, suffix: &str) -> bool {
+        self.string_value().is_some_and(|x| x.ends_with(suffix))
+    }
+
+    /// Check if a string entry matches a SQL LIKE pattern, case sensitive
+    ///
+    /// `%` matches any sequence of characters and `_` matches a single character
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec!["A@mail.com".into()]);
+    /// db.table("users").unwrap().insert(vec!["b@mail.org".into()]);
+    /// let found = db.table("users").unwrap().get_where(|x| x.row("email").like("A%@%.com"));
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].row("email").like("_@mail.___"), true);
+    ///
+    /// // Many `%` do not make matching slow
+    /// db.table("users").unwrap().insert(vec!["a".repeat(200).into()]);
+    /// let found = db.table("users").unwrap().get_where(|x| x.row("email").like("%a%a%a%a%a%a%a%a%a%a%%%b"));
+    /// assert!(found.is_empty());
+    /// ```
+    pub fn like(&self, pattern: &str) -> bool {
+        self.like_with_options(pattern, LikeOptions::default())
+    }
+
+    /// Check if a string entry matches a SQL LIKE pattern with given options
+    ///
+    /// Returns false if the entry does not exist or is not a string
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{LikeOptions, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec!["Ahmet".into()]);
+    /// let options = LikeOptions { case_sensitive: false };
+    /// let found = db.table("users").unwrap().get_where(|x| {
+    ///     x.row("name").like_with_options("ahm%", options.clone())
+    /// });
+    /// assert_eq!(found.len(), 1);
+    /// ```
+    pub fn like_with_options(&self, pattern: &str, options: LikeOptions) -> bool {
+        let value = match &self.entry {
+            Some(entry) => match &entry.value.rtype {
+                Types::String(e) => e.clone(),
+                _ => return false,
+            },
+            None => return false,
+        };
+        let (value, pattern) = if options.case_sensitive {
+            (value, pattern.to_string())
+        } else {
+            (value.to_lowercase(), pattern.to_lowercase())
+        };
+        like_match(
+            &value.chars().collect::<Vec<char>>(),
+            &pattern.chars().collect::<Vec<char>>(),
+        )
+    }
+
+    /// Check if a string entry matches the given regex pattern
+    ///
+    /// Returns `Ok(false)` if the entry does not exist or is not a string
+    /// ## Errors
+    /// Returns a [`RegexError`] if the pattern is invalid
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec!["ahmet@mail.com".into()]);
+    /// let found = db.table("users").unwrap().get_where(|x| {
+    ///     x.row("email").matches_regex(r"^[a-z]+@mail\.com$").unwrap()
+    /// });
+    /// assert_eq!(found.len(), 1);
+    /// assert!(found[0].row("email").matches_regex("(").is_err());
+    /// ```
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn matches_regex(&self, pattern: &str) -> Result<bool, RegexError> {
+        let regex = regex::Regex::new(pattern).map_err(RegexError)?;
+        Ok(self.compiled_regex_filter(&regex))
+    }
+
+    /// Check if a string entry matches an already compiled regex
+    ///
+    /// Prefer this over [`RowQuery::matches_regex`] in filters to avoid compiling the pattern for every row
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec!["ahmet@mail.com".into()]);
+    /// db.table("users").unwrap().insert(vec!["seda@mail.org".into()]);
+    /// let regex = regex::Regex::new(r"\.org$").unwrap();
+    /// let found = db.table("users").unwrap().get_where(|x| x.row("email").compiled_regex_filter(&regex));
+    /// assert_eq!(found.len(), 1);
+    /// ```
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn compiled_regex_filter(&self, regex: &regex::Regex) -> bool {
+        match &self.entry {
+            Some(entry) => match &entry.value.rtype {
+                Types::String(e) => regex.is_match(e),
+                _ => false,
+            },
+            None => false,
+        }
+    }
+}
+
+/// LIKE matcher, `%` matches any sequence and `_` matches a single char
+///
+/// Iterative wildcard match that only backtracks to the last `%`, so runtime is at most `value.len() * pattern.len()`
+fn like_match(value: &[char], pattern: &[char]) -> bool {
+    let mut pattern = pattern.to_vec();
+    pattern.dedup_by(|a, b| *a == '%' && *b == '%');
+    let (mut v, mut p) = (0, 0);
+    // Position of the last `%` and the value position it is currently matched up to
+    let mut star: Option<(usize, usize)> = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                star = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == '_' || *c == value[v] => {
+                v += 1;
+                p += 1;
+            }
+            _ => match star {
+                // Let the last `%` take one more char and retry the rest of the pattern
+                Some((star_p, star_v)) => {
+                    star = Some((star_p, star_v + 1));
+                    p = star_p + 1;
+                    v = star_v + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '%')
 }
 
 impl Entries {
@@ -1251,7 +2562,159 @@ impl Entries {
                 Some(x) => Some(x.clone()),
                 None => None,
             },
+            strict: true,
+        }
+    }
+
+    /// Collect entries into a map of key and value
+    /// ## Returns
+    /// [`HashMap<String, Types>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![
+    ///     "John".into(),
+    ///     12_i64.into()
+    /// ]);
+    /// let map = db.table("users").unwrap().get_at(0).unwrap().to_map();
+    /// assert_eq!(map["name"], Types::String("John".to_string()));
+    /// assert_eq!(map["age"], Types::I64(12));
+    /// ```
+    pub fn to_map(&self) -> HashMap<String, Types> {
+        self.entries
+            .iter()
+            .map(|x| (x.key.clone(), x.value.get_type()))
+            .collect()
+    }
+
+    /// Compare entries with another row
+    /// ## Arguments
+    /// * `other` - Entries to compare against, treated as the newer row
+    /// ## Returns
+    /// [`Vec<EntryDiff>`] Empty if both rows are equal
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{EntryDiff, TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 12_i64.into()]).unwrap();
+    /// table.insert(vec!["John".into(), 13_i64.into()]).unwrap();
+    /// let old = table.get_at(0).unwrap();
+    /// let new = table.get_at(1).unwrap();
+    /// assert_eq!(
+    ///     old.diff(&new),
+    ///     vec![EntryDiff::Changed {
+    ///         key: "age".to_string(),
+    ///         old: Types::I64(12),
+    ///         new: Types::I64(13),
+    ///     }]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Entries) -> Vec<EntryDiff> {
+        let mut diffs = Vec::new();
+        for entry in &self.entries {
+            match other.get(&entry.key) {
+                Some(other_entry) => {
+                    if other_entry.value != entry.value {
+                        diffs.push(EntryDiff::Changed {
+                            key: entry.key.clone(),
+                            old: entry.value.get_type(),
+                            new: other_entry.value.get_type(),
+                        });
+                    }
+                }
+                None => diffs.push(EntryDiff::Removed {
+                    key: entry.key.clone(),
+                    old: entry.value.get_type(),
+                }),
+            }
+        }
+        for other_entry in &other.entries {
+            if self.get(&other_entry.key).is_none() {
+                diffs.push(EntryDiff::Added {
+                    key: other_entry.key.clone(),
+                    new: other_entry.value.get_type(),
+                });
+            }
+        }
+        diffs
+    }
+
+    /// Convert entries to a JSON object
+    /// ## Returns
+    /// [`serde_json::Value::Object`] Keys are column names, values are their natural JSON representation
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    ///     TableRow::new("tags", TypeDefs::array_of(TypeDefs::String)),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![
+    ///     "John".into(),
+    ///     12_i64.into(),
+    ///     vec!["admin"].into(),
+    /// ]);
+    /// let json = db.table("users").unwrap().get_at(0).unwrap().to_json_value();
+    /// assert_eq!(json, serde_json::json!({ "name": "John", "age": 12, "tags": ["admin"] }));
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::Value::Object(
+            self.entries
+                .iter()
+                .map(|x| (x.key.clone(), x.value.rtype.to_json_value()))
+                .collect(),
+        )
+    }
+
+    /// Combine two rows, entries of `other` are appended after entries of this row
+    /// ## Arguments
+    /// * `other` - Row to append
+    /// ## Returns
+    /// * [`Ok<Entries>`] Combined row
+    /// * [`Err<MergeError>`] If a key exists in both rows
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{MergeError, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("id", TypeDefs::I64),
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// db.create_table("orders", vec![
+    ///    TableRow::new("user_id", TypeDefs::I64),
+    ///    TableRow::new("total", TypeDefs::F64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![1_i64.into(), "John".into()]).unwrap();
+    /// db.table("orders").unwrap().insert(vec![1_i64.into(), 9.5_f64.into()]).unwrap();
+    ///
+    /// let user = db.table("users").unwrap().get_at(0).unwrap();
+    /// let order = db.table("orders").unwrap().get_at(0).unwrap();
+    /// let joined = user.merge(&order).unwrap();
+    /// assert_eq!(joined.entries.len(), 4);
+    /// assert_eq!(joined.get("total").unwrap().get::<f64>(), 9.5);
+    /// assert_eq!(user.merge(&user).err(), Some(MergeError::DuplicateKey("id".to_string())));
+    /// ```
+    pub fn merge(&self, other: &Entries) -> Result<Entries, MergeError> {
+        if let Some(duplicate) = other.entries.iter().find(|x| self.get(&x.key).is_some()) {
+            return Err(MergeError::DuplicateKey(duplicate.key.clone()));
         }
+        let mut entries = self.entries.clone();
+        entries.extend(other.entries.iter().cloned());
+        Ok(Entries { entries })
     }
 }
 
@@ -1289,212 +2752,3403 @@ impl TableRow {
 }
 
 impl Table {
-    /// Get table name
-    /// ## Returns
-    /// [`&str`] Table name
+    /// Creates an empty table
+    pub(crate) fn new(name: &str, headers: Vec<TableRow>) -> Table {
+        Table {
+            name: name.to_owned(),
+            headers,
+            columns: vec![],
+            #[cfg(feature = "events")]
+            subscribers: vec![],
+            row_cache: None,
+            column_metadata: HashMap::new(),
+            unique_columns: vec![],
+            column_defaults: HashMap::new(),
+            lru_indexes: HashMap::new(),
+            bloom_indexes: utils::BloomIndexes::default(),
+            unique_index: utils::UniqueIndex::default(),
+        }
+    }
+
+    /// Cache the last `capacity` rows returned by [`Table::get_at`]
+    ///
+    /// Cache is cleared on every mutating operation, a `capacity` of 0 disables caching
     /// ## Example
     /// ```
-    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
     ///     TableRow::new("name", TypeDefs::String),
     ///     TableRow::new("age", TypeDefs::I64),
     /// ]);
-    /// assert_eq!(db.table("users").unwrap().get_name(), "users");
+    /// let table = db.table("users").unwrap().with_lru_cache(100);
+    /// table.insert(vec!["John".into(), 12_i64.into()]).unwrap();
+    /// assert_eq!(table.get_at(0).unwrap().get("age").unwrap().get::<i64>(), 12);
+    ///
+    /// table.inc_where(|x| x.row("name").is("John"), "age").unwrap();
+    /// assert_eq!(table.get_at(0).unwrap().get("age").unwrap().get::<i64>(), 13);
     /// ```
-    pub fn get_name(&self) -> &str {
-        &self.name
+    pub fn with_lru_cache(&mut self, capacity: usize) -> &mut Table {
+        self.row_cache = if capacity == 0 {
+            None
+        } else {
+            Some(utils::LruCache::new(capacity))
+        };
+        self
     }
 
-    ///Get Headers
+    /// Whether table has an index
+    pub(crate) fn has_index(&self) -> bool {
+        !self.lru_indexes.is_empty() || !self.bloom_indexes.lock().is_empty()
+    }
+
+    /// Fill trailing columns from defaults and check length and types of a row before it is written
+    fn validate_row(&self, mut row: Vec<SafeType>) -> Result<Vec<SafeType>, Vec<String>> {
+        for header in self.headers.iter().skip(row.len()) {
+            match self.column_defaults.get(&header.key) {
+                Some(default) => row.push(default.clone()),
+                None => break,
+            }
+        }
+        if row.len() != self.headers.len() {
+            return Err(vec![format!(
+                "Length mismatch, expected {}, got {} length of column",
+                self.headers.len(),
+                row.len()
+            )]);
+        }
+        let errors: Vec<String> = self
+            .headers
+            .iter()
+            .zip(row.iter())
+            .filter_map(|(header, value)| {
+                if header.rtype != value.get_type_def() {
+                    Some(format!(
+                        "Type mismatch, expected {}, got {} on column {}",
+                        header.rtype,
+                        value.get_type_def(),
+                        header.key
+                    ))
+                } else if !header.rtype.fits_type_id() {
+                    Some(format!(
+                        "Tuple on column {} has more than {} elements",
+                        header.key,
+                        u8::MAX
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(row)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Values of unique columns, taken out of the index or built from rows if a change emptied it
+    ///
+    /// `None` if table has no unique column. Writes check new values against the sets
+    /// and put them back with [`Table::keep_unique_values`] once rows are written
+    fn take_unique_values(&self) -> Option<utils::UniqueSets> {
+        if self.unique_columns.is_empty() {
+            return None;
+        }
+        if let Some(sets) = self.unique_index.take() {
+            return Some(sets);
+        }
+        Some(
+            self.headers
+                .iter()
+                .enumerate()
+                .filter(|(_, header)| self.unique_columns.contains(&header.key))
+                .map(|(position, _)| {
+                    let values = self
+                        .columns
+                        .iter()
+                        .map(|row| utils::value_bytes(&row[position]))
+                        .collect();
+                    (position, values)
+                })
+                .collect(),
+        )
+    }
+
+    /// Store sets taken by [`Table::take_unique_values`] after rows are written
+    fn keep_unique_values(&self, sets: Option<utils::UniqueSets>) {
+        if let Some(sets) = sets {
+            self.unique_index.put(sets);
+        }
+    }
+
+    /// Drop values of rows that are about to be removed or changed from unique sets
+    fn remove_unique_values<'a>(
+        sets: &mut utils::UniqueSets,
+        rows: impl IntoIterator<Item = &'a [SafeType]>,
+    ) {
+        for row in rows {
+            for (position, values) in sets.iter_mut() {
+                values.remove(&utils::value_bytes(&row[*position]));
+            }
+        }
+    }
+
+    /// Add values of `(row index, row)` pairs to unique sets
+    ///
+    /// Returns `(column position, row index)` of the first value that is already taken, sets are not usable after an error
+    fn add_unique_values<'a>(
+        sets: &mut utils::UniqueSets,
+        rows: impl IntoIterator<Item = (usize, &'a [SafeType])>,
+    ) -> Result<(), (usize, usize)> {
+        for (index, row) in rows {
+            for (position, values) in sets.iter_mut() {
+                if !values.insert(utils::value_bytes(&row[*position])) {
+                    return Err((*position, index));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn unique_error(&self, (position, index): (usize, usize)) -> Vec<String> {
+        vec![format!(
+            "Unique constraint violated on column {} on row {}",
+            self.headers[position].key, index
+        )]
+    }
+
+    /// Check rows changed in place against unique columns, putting `previous` rows back if a value repeats
+    ///
+    /// `previous` holds each changed row as it was before the change, by row index
+    fn check_changed_rows(
+        &mut self,
+        sets: Option<utils::UniqueSets>,
+        previous: Vec<(usize, Vec<SafeType>)>,
+    ) -> Result<(), Vec<String>> {
+        let mut sets = match sets {
+            Some(sets) => sets,
+            None => return Ok(()),
+        };
+        Self::remove_unique_values(&mut sets, previous.iter().map(|(_, row)| row.as_slice()));
+        let changed = previous
+            .iter()
+            .map(|(index, _)| (*index, self.columns[*index].as_slice()));
+        if let Err(violation) = Self::add_unique_values(&mut sets, changed) {
+            let errors = self.unique_error(violation);
+            for (index, row) in previous {
+                self.columns[index] = row;
+            }
+            return Err(errors);
+        }
+        self.unique_index.put(sets);
+        Ok(())
+    }
+
+    /// Drop cached rows and index entries, called by every mutating operation
+    pub(crate) fn invalidate_cache(&self) {
+        if let Some(cache) = &self.row_cache {
+            cache.clear();
+        }
+        for index in self.lru_indexes.values() {
+            index.clear();
+        }
+        for index in self.bloom_indexes.lock().values_mut() {
+            index.mark_stale();
+        }
+        self.unique_index.clear();
+    }
+
+    /// Index a column, keeping row positions of the `capacity` most recently looked up values
+    ///
+    /// Used by [`Table::get_indexed`], values missing from the index are found with a linear scan and added to it.
+    /// Index is kept in memory only and emptied on every change to the table
+    /// ## Arguments
+    /// * `column` - Name of the column
+    /// * `capacity` - Number of values kept in the index
     /// ## Returns
-    /// * [`Vec<TableRow>`] Vector of table rows
+    /// * [`Ok<()>`]
+    /// * [`Err<Vec<String>>`] If column does not exist
     /// ## Example
     /// ```
     /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
-    ///    TableRow::new("name", TypeDefs::String),
-    ///    TableRow::new("age", TypeDefs::I64),
-    /// ]);
-    /// let headers = db.table("users").unwrap().get_headers();
-    /// assert_eq!(headers.len(), 2);
-    /// assert_eq!(headers[0].key, "name");
-    /// assert_eq!(headers[0].rtype, TypeDefs::String);
+    ///     TableRow::new("email", TypeDefs::String),
+    /// ]).unwrap();
+    /// let table = db.table("users").unwrap();
+    /// table.create_lru_index("email", 100).unwrap();
+    /// assert!(table.create_lru_index("age", 100).is_err());
+    /// assert_eq!(db.list_tables()[0].has_index, true);
     /// ```
-    pub fn get_headers(&self) -> Vec<TableRow> {
-        self.headers.clone()
+    pub fn create_lru_index(&mut self, column: &str, capacity: usize) -> Result<(), Vec<String>> {
+        if !self.has_column(column) {
+            return Err(vec![format!("Could not find key '{}' in table", column)]);
+        }
+        self.lru_indexes
+            .insert(column.to_string(), utils::LruCache::new(capacity));
+        Ok(())
     }
 
-    /// Get all columns as a vector of Entries
+    /// Get rows where `column` equals `value`, using the index created by [`Table::create_lru_index`]
+    ///
+    /// Columns without an index are always scanned, a value of another type than the column matches no row
+    /// ## Arguments
+    /// * `column` - Name of the column
+    /// * `value` - Value to find
     /// ## Returns
-    /// * [`Vec<Entries>`]
+    /// [`Vec<Entries>`] Matching rows in table order
     /// ## Example
     /// ```
-    /// use safe_en::{Database, table::{Entries, Entry, Types, TypeDefs, TableRow}};
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
-    ///   TableRow::new("name", TypeDefs::String),
-    ///   TableRow::new("age", TypeDefs::I64),
-    /// ]);
-    /// db.table("users").unwrap().insert(vec![
-    ///   "John".into(),
-    ///   12_i64.into()
-    /// ]);
-    /// let entries = db.table("users").unwrap().get_all();
+    ///     TableRow::new("email", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]).unwrap();
+    /// let table = db.table("users").unwrap();
+    /// table.create_lru_index("email", 2).unwrap();
+    /// table.insert(vec!["john@mail.com".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["jane@mail.com".into(), 21_i64.into()]).unwrap();
+    ///
+    /// let found = table.get_indexed("email", &"jane@mail.com".into());
+    /// assert_eq!(found[0].get("age").unwrap().get::<i64>(), 21);
+    /// // Second lookup is served by the index
+    /// assert_eq!(table.get_indexed("email", &"jane@mail.com".into()).len(), 1);
+    /// assert!(table.get_indexed("email", &"none@mail.com".into()).is_empty());
+    ///
+    /// table.create_lru_index("age", 2).unwrap();
+    /// assert!(table.get_indexed("age", &21_u64.into()).is_empty());
+    /// assert_eq!(table.get_indexed("age", &21_i64.into()).len(), 1);
     /// ```
-    pub fn get_all(&self) -> Vec<Entries> {
-        let mut all = Vec::new();
-        for i in 0..self.columns.len() {
-            let mut entries = Vec::new();
-            for j in 0..self.columns[i].len() {
-                entries.push(Entry {
-                    key: self.headers[j].key.clone(),
-                    value: self.columns[i][j].clone(),
-                });
-            }
-            all.push(Entries { entries });
+    pub fn get_indexed(&self, column: &str, value: &SafeType) -> Vec<Entries> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return vec![],
+        };
+        // Values of different types can serialize to the same bytes, keep them out of the index
+        if value.get_type_def() != self.headers[position].rtype {
+            return vec![];
         }
-        all
+        let key = utils::value_bytes(value);
+        let index = self.lru_indexes.get(column);
+        let positions = match index.and_then(|x| x.get(&key)) {
+            Some(e) => e,
+            None => {
+                let positions: Vec<usize> = self
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| &row[position] == value)
+                    .map(|(index, _)| index)
+                    .collect();
+                if let Some(index) = index {
+                    index.put(key, positions.clone());
+                }
+                positions
+            }
+        };
+        positions
+            .into_iter()
+            .filter_map(|index| self.row_at(index))
+            .collect()
     }
 
-    /// Get row by index
+    /// Create a Bloom filter over `column` for fast membership tests with [`Table::has_value_approx`]
+    ///
+    /// Filter is kept in memory only and rebuilt on the first lookup after a change to the table
     /// ## Arguments
-    /// * `index` - Index of row
+    /// * `column` - Name of the column
+    /// * `expected_items` - Number of values the filter is sized for
+    /// * `false_positive_rate` - Chance of a positive answer for a missing value, between 0 and 1
     /// ## Returns
-    /// [`Option<Entries>`]
+    /// * [`Ok<()>`]
+    /// * [`Err<Vec<String>>`] If column does not exist or `false_positive_rate` is out of range
     /// ## Example
     /// ```
-    /// use safe_en::{Database, table::{Entries, Entry, Types, TypeDefs, TableRow}};
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
-    ///     TableRow::new("name", TypeDefs::String),
-    ///     TableRow::new("age", TypeDefs::I64),
-    /// ]);
-    /// db.table("users").unwrap().insert(vec![
-    ///     "John".into(),
-    ///     12_i64.into()
-    /// ]);
-    /// let entries = db.table("users").unwrap().get_at(0).unwrap();
+    ///     TableRow::new("email", TypeDefs::String),
+    /// ]).unwrap();
+    /// let table = db.table("users").unwrap();
+    /// table.create_bloom_filter("email", 1000, 0.01).unwrap();
+    /// assert!(table.create_bloom_filter("age", 1000, 0.01).is_err());
+    /// assert!(table.create_bloom_filter("email", 1000, 1.5).is_err());
     /// ```
-    pub fn get_at(&self, index: usize) -> Option<Entries> {
-        let column = self.columns.get(index)?;
-        let mut entries = Vec::new();
-        for i in 0..column.len() {
-            entries.push(Entry {
-                key: self.headers[i].key.clone(),
-                value: column[i].clone(),
-            });
+    pub fn create_bloom_filter(
+        &mut self,
+        column: &str,
+        expected_items: usize,
+        false_positive_rate: f64,
+    ) -> Result<(), Vec<String>> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return Err(vec![format!("Could not find key '{}' in table", column)]),
+        };
+        if !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(vec![format!(
+                "False positive rate must be between 0 and 1, got {}",
+                false_positive_rate
+            )]);
         }
-        Some(Entries { entries })
+        let mut index = utils::BloomIndex::new(expected_items, false_positive_rate);
+        index.rebuild(self.columns.iter().map(|row| &row[position]));
+        self.bloom_indexes.lock().insert(column.to_string(), index);
+        Ok(())
     }
 
-    /// Remove a row by filter
+    /// Check whether any row has `value` in `column`, using the filter created by [`Table::create_bloom_filter`]
+    ///
+    /// A `false` answer is definitive, a `true` answer may be a false positive and can be confirmed with a scan.
+    /// Columns without a filter are always scanned
     /// ## Arguments
-    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `column` - Name of the column
+    /// * `value` - Value to find
     /// ## Returns
-    /// [`usize`] Effected row length
+    /// [`bool`] `false` if no row has the value
     /// ## Example
     /// ```
-    /// use safe_en::Database;
-    /// use safe_en::table::{TableRow, TypeDefs};
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
-    ///    TableRow::new("name", TypeDefs::String),
-    ///    TableRow::new("age", TypeDefs::I64),
-    /// ]);
-    /// db.table("users").unwrap().remove_where(|entry| entry.row("name").is("Ahmet".to_string()));
+    ///     TableRow::new("email", TypeDefs::String),
+    /// ]).unwrap();
+    /// let table = db.table("users").unwrap();
+    /// table.create_bloom_filter("email", 100, 0.01).unwrap();
+    /// table.insert(vec!["john@mail.com".into()]).unwrap();
+    ///
+    /// assert!(table.has_value_approx("email", &"john@mail.com".into()));
+    /// if table.has_value_approx("email", &"jane@mail.com".into()) {
+    ///     // Possible false positive, confirm with a scan
+    ///     assert!(table.get_indexed("email", &"jane@mail.com".into()).is_empty());
+    /// }
+    ///
+    /// // -0.0 and 0.0 are equal values
+    /// db.create_table("readings", vec![TableRow::new("value", TypeDefs::F64)]).unwrap();
+    /// let readings = db.table("readings").unwrap();
+    /// readings.create_bloom_filter("value", 100, 0.01).unwrap();
+    /// readings.insert(vec![(-0.0_f64).into()]).unwrap();
+    /// assert!(readings.has_value_approx("value", &0.0_f64.into()));
     /// ```
-    pub fn remove_where<E: Fn(Entries) -> bool + Clone + Sized>(&mut self, filter: E) -> usize {
-        let mut found_entries = Vec::new();
-        for (index, entries) in self.columns.iter().enumerate() {
-            let fake_entries = Entries {
-                entries: entries
-                    .iter()
-                    .enumerate()
-                    .map(|(ix, value)| Entry {
-                        key: self.headers[ix].key.clone(),
-                        value: value.clone(),
-                    })
-                    .collect(),
-            };
+    pub fn has_value_approx(&self, column: &str, value: &SafeType) -> bool {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return false,
+        };
+        match self.bloom_might_contain(column, position, value, false) {
+            Some(found) => found,
+            None => self.columns.iter().any(|row| &row[position] == value),
+        }
+    }
 
-            if filter(fake_entries.clone()) {
-                found_entries.push(index);
-            }
+    /// Check whether any row has `value` in the string `column`
+    ///
+    /// A Bloom filter is built for the column on first use, so values missing from the table are answered without a scan.
+    /// Filter is rebuilt on the first lookup after a change to the table
+    /// ## Arguments
+    /// * `column` - Name of the string column
+    /// * `value` - Value to find
+    /// ## Returns
+    /// [`bool`] `true` if a row has the value, `false` if not or column is not a string column
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("email", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]).unwrap();
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["john@mail.com".into(), 18_i64.into()]).unwrap();
+    ///
+    /// assert!(table.has_string_value("email", "john@mail.com"));
+    /// assert!(!table.has_string_value("email", "jane@mail.com"));
+    /// table.insert(vec!["jane@mail.com".into(), 21_i64.into()]).unwrap();
+    /// assert!(table.has_string_value("email", "jane@mail.com"));
+    /// assert!(!table.has_string_value("age", "18"));
+    /// ```
+    pub fn has_string_value(&self, column: &str, value: &str) -> bool {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return false,
+        };
+        if self.headers[position].rtype != TypeDefs::String {
+            return false;
         }
+        let value: SafeType = value.into();
+        if self.bloom_might_contain(column, position, &value, true) == Some(false) {
+            return false;
+        }
+        self.columns.iter().any(|row| row[position] == value)
+    }
 
-        for i in &found_entries {
-            self.columns.remove(*i);
+    /// Query the Bloom filter of a column, rebuilding it if stale. With `create` a missing filter is built first,
+    /// otherwise `None` is returned for columns without a filter
+    fn bloom_might_contain(
+        &self,
+        column: &str,
+        position: usize,
+        value: &SafeType,
+        create: bool,
+    ) -> Option<bool> {
+        let mut filters = self.bloom_indexes.lock();
+        if create && !filters.contains_key(column) {
+            let mut index = utils::BloomIndex::new(self.columns.len() * 2, 0.01);
+            index.mark_stale();
+            filters.insert(column.to_string(), index);
         }
-        found_entries.len()
+        let index = filters.get_mut(column)?;
+        if index.is_stale() {
+            index.rebuild(self.columns.iter().map(|row| &row[position]));
+        }
+        Some(index.might_contain(value))
     }
 
-    /// Get the value of a column by filter
-    /// ## Arguments
-    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
-    /// * `value` - Value to set
+    /// Subscribe to changes of the table
     /// ## Returns
-    /// [`Vec<Entries>`]
+    /// [`std::sync::mpsc::Receiver<TableEvent>`] Receives an event for every inserted, updated or deleted row
     /// ## Example
     /// ```
-    /// use safe_en::Database;
-    /// use safe_en::table::{TableRow, TypeDefs};
+    /// use safe_en::{Database, table::{EventType, TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
-    ///    TableRow::new("name", TypeDefs::String),
-    ///   TableRow::new("age", TypeDefs::I64),
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
     /// ]);
-    /// db.table("users").unwrap().get_where(|entry| entry.row("name").is("Ahmet".to_string()));
+    /// let table = db.table("users").unwrap();
+    /// let events = table.subscribe();
+    /// table.insert(vec!["John".into(), 12_i64.into()]).unwrap();
+    /// table.inc_where(|x| x.row("name").is("John"), "age").unwrap();
+    /// table.remove_where(|x| x.row("name").is("John"));
+    ///
+    /// let received: Vec<EventType> = events.try_iter().map(|x| x.event_type).collect();
+    /// assert_eq!(received, vec![EventType::Insert, EventType::Update, EventType::Delete]);
+    ///
+    /// // Clones do not inherit subscribers
+    /// let mut copy = table.clone();
+    /// copy.insert(vec!["Jane".into(), 21_i64.into()]).unwrap();
+    /// assert!(events.try_recv().is_err());
     /// ```
-    pub fn get_where<E: Fn(Entries) -> bool + Clone + Sized>(&self, filter: E) -> Vec<Entries> {
-        let mut found_entries = Vec::new();
-        for entries in self.columns.iter() {
-            let fake_entries = Entries {
-                entries: entries
-                    .iter()
-                    .enumerate()
-                    .map(|(ix, value)| Entry {
-                        key: self.headers[ix].key.clone(),
-                        value: value.clone(),
-                    })
-                    .collect(),
-            };
+    #[cfg(feature = "events")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+    pub fn subscribe(&mut self) -> std::sync::mpsc::Receiver<TableEvent> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.subscribers.push(sender);
+        receiver
+    }
 
-            if filter(fake_entries.clone()) {
-                found_entries.push(fake_entries);
-            }
+    /// Send event to subscribers, dropping the ones that are disconnected
+    #[cfg(feature = "events")]
+    pub(crate) fn emit(&mut self, event_type: EventType, row: &[SafeType]) {
+        if self.subscribers.is_empty() {
+            return;
         }
-        found_entries
+        let entries = Entries {
+            entries: row
+                .iter()
+                .enumerate()
+                .map(|(ix, value)| Entry {
+                    key: self.headers[ix].key.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+        };
+        self.subscribers.retain(|subscriber| {
+            subscriber
+                .send(TableEvent {
+                    event_type: event_type.clone(),
+                    row: Some(entries.clone()),
+                })
+                .is_ok()
+        });
     }
 
-    /// Increase the value of a number by filter
-    /// ## Arguments
-    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// Get table name
     /// ## Returns
-    /// * [`Ok<()>`]
-    /// * [`Err<Vec<String>>`] - Error messages
+    /// [`&str`] Table name
     /// ## Example
-    /// ```rust
-    /// use safe_en::Database;
-    /// use safe_en::table::{TableRow, TypeDefs};
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// assert_eq!(db.table("users").unwrap().get_name(), "users");
+    /// ```
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    ///Get Headers
+    /// ## Returns
+    /// * [`Vec<TableRow>`] Vector of table rows
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
     ///    TableRow::new("name", TypeDefs::String),
     ///    TableRow::new("age", TypeDefs::I64),
     /// ]);
-    /// db.table("users").unwrap().inc_where(|x| {
-    ///     x.row("age").exists()
-    /// }, "age");
-    /// //Increases all ages by 1
+    /// let headers = db.table("users").unwrap().get_headers();
+    /// assert_eq!(headers.len(), 2);
+    /// assert_eq!(headers[0].key, "name");
+    /// assert_eq!(headers[0].rtype, TypeDefs::String);
     /// ```
-    pub fn inc_where<E: Fn(Entries) -> bool + Clone + Sized>(
-        &mut self,
-        filter: E,
-        row: &str,
-    ) -> Result<(), Vec<String>> {
-        let mut errors = vec![];
-        for entries in &mut self.columns {
+    pub fn get_headers(&self) -> Vec<TableRow> {
+        self.headers.clone()
+    }
+
+    /// Copy of the table schema without any rows, to create an empty table with the same columns
+    /// ## Returns
+    /// * [`Vec<TableRow>`] Vector of table rows
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec!["Ahmet".into(), 18_i64.into()]).unwrap();
+    ///
+    /// let schema = db.table("users").unwrap().clone_schema();
+    /// db.create_table("users_archive", schema).unwrap();
+    /// let archive = db.table("users_archive").unwrap();
+    /// assert_eq!(archive.get_headers()[1].rtype, TypeDefs::I64);
+    /// assert_eq!(archive.get_all().len(), 0);
+    /// ```
+    pub fn clone_schema(&self) -> Vec<TableRow> {
+        self.headers.clone()
+    }
+
+    /// Get headers matching a predicate
+    /// ## Arguments
+    /// * `pred` - Function returning true for headers to keep
+    /// ## Returns
+    /// [`Vec<&TableRow>`] Matching headers in column order
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// let strings = table.filter_headers(|x| x.rtype == TypeDefs::String);
+    /// assert_eq!(strings.iter().map(|x| x.key.as_str()).collect::<Vec<_>>(), vec!["name", "email"]);
+    /// ```
+    pub fn filter_headers<E: Fn(&TableRow) -> bool>(&self, pred: E) -> Vec<&TableRow> {
+        self.headers.iter().filter(|x| pred(x)).collect()
+    }
+
+    /// Set a metadata annotation on a column, metadata is kept in memory and not saved to file
+    /// ## Arguments
+    /// * `column` - Name of the column
+    /// * `key` - Metadata key
+    /// * `value` - Metadata value
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<MigrationError>`] If column does not exist
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.set_column_metadata("email", "description", "Login address").unwrap();
+    /// assert!(table.set_column_metadata("age", "description", "Age").is_err());
+    /// ```
+    pub fn set_column_metadata(
+        &mut self,
+        column: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<(), MigrationError> {
+        if !self.has_column(column) {
+            return Err(MigrationError::ColumnNotFound(column.to_string()));
+        }
+        self.column_metadata
+            .entry(column.to_string())
+            .or_default()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// Get headers paired with their metadata
+    /// ## Returns
+    /// * [`Vec<(TableRow, HashMap<String, String>)>`] Columns without metadata get an empty map
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("email", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.set_column_metadata("email", "description", "Login address").unwrap();
+    /// let headers = table.get_headers_with_metadata();
+    /// assert!(headers[0].1.is_empty());
+    /// assert_eq!(headers[1].0.key, "email");
+    /// assert_eq!(headers[1].1["description"], "Login address");
+    /// ```
+    pub fn get_headers_with_metadata(&self) -> Vec<(TableRow, HashMap<String, String>)> {
+        self.headers
+            .iter()
+            .map(|header| {
+                (
+                    header.clone(),
+                    self.column_metadata
+                        .get(&header.key)
+                        .cloned()
+                        .unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// Check if table has a column with given name
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// assert_eq!(db.table("users").unwrap().has_column("name"), true);
+    /// assert_eq!(db.table("users").unwrap().has_column("age"), false);
+    /// ```
+    pub fn has_column(&self, name: &str) -> bool {
+        self.headers.iter().any(|x| x.key == name)
+    }
+
+    /// Check if table has a row at given index
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec!["John".into()]).unwrap();
+    /// assert_eq!(db.table("users").unwrap().has_row_at(0), true);
+    /// assert_eq!(db.table("users").unwrap().has_row_at(1), false);
+    /// ```
+    pub fn has_row_at(&self, index: usize) -> bool {
+        index < self.columns.len()
+    }
+
+    /// Check if table has a row equal to `row` on every column
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 18_i64.into()]).unwrap();
+    /// assert_eq!(table.contains(&["John".into(), 18_i64.into()]), true);
+    /// assert_eq!(table.contains(&["John".into(), 19_i64.into()]), false);
+    /// ```
+    pub fn contains(&self, row: &[SafeType]) -> bool {
+        self.columns.iter().any(|column| column.as_slice() == row)
+    }
+
+    /// Find rows equal to an earlier row on every column
+    /// ## Returns
+    /// [`Vec<(usize, usize)>`] Index of the first row with the values and index of the duplicate row
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["Jane".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["John".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["John".into(), 18_i64.into()]).unwrap();
+    /// assert_eq!(table.detect_duplicates(), vec![(0, 2), (0, 3)]);
+    /// ```
+    pub fn detect_duplicates(&self) -> Vec<(usize, usize)> {
+        let mut first_seen: HashMap<Vec<u8>, usize> = HashMap::new();
+        let mut duplicates = vec![];
+        for (index, row) in self.columns.iter().enumerate() {
+            let bytes: Vec<u8> = row.iter().flat_map(utils::value_bytes).collect();
+            match first_seen.get(&bytes) {
+                Some(first) => duplicates.push((*first, index)),
+                None => {
+                    first_seen.insert(bytes, index);
+                }
+            }
+        }
+        duplicates
+    }
+
+    /// Add missing columns, existing rows get the zero value of the column type
+    /// ## Arguments
+    /// * `required` - Pairs of column name and type
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<MigrationError>`] If a column exists with a different type or a new tuple column has more than 255 elements, table is left untouched
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{MigrationError, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into()]).unwrap();
+    /// table.ensure_columns(&[("name", TypeDefs::String), ("age", TypeDefs::I64)]).unwrap();
+    /// assert_eq!(table.get_at(0).unwrap().get("age").unwrap().get::<i64>(), 0);
+    ///
+    /// assert_eq!(
+    ///     table.ensure_columns(&[("age", TypeDefs::String)]),
+    ///     Err(MigrationError::TypeConflict {
+    ///         column: "age".to_string(),
+    ///         expected: TypeDefs::String,
+    ///         got: TypeDefs::I64,
+    ///     })
+    /// );
+    /// ```
+    pub fn ensure_columns(&mut self, required: &[(&str, TypeDefs)]) -> Result<(), MigrationError> {
+        let mut missing = vec![];
+        for (key, rtype) in required {
+            let existing = self
+                .headers
+                .iter()
+                .chain(missing.iter())
+                .find(|x| &x.key == key);
+            match existing {
+                Some(header) => {
+                    if &header.rtype != rtype {
+                        return Err(MigrationError::TypeConflict {
+                            column: key.to_string(),
+                            expected: rtype.clone(),
+                            got: header.rtype.clone(),
+                        });
+                    }
+                }
+                None => {
+                    if !rtype.fits_type_id() {
+                        return Err(MigrationError::Failed(format!(
+                            "Tuple on column '{}' has more than {} elements",
+                            key,
+                            u8::MAX
+                        )));
+                    }
+                    missing.push(TableRow::new(key, rtype.clone()))
+                }
+            }
+        }
+
+        self.invalidate_cache();
+        for header in missing {
+            let default_value = header.rtype.default_value();
+            for column in self.columns.iter_mut() {
+                column.push(default_value.clone());
+            }
+            self.headers.push(header);
+        }
+        Ok(())
+    }
+
+    /// Compare schemas of two tables, ignoring their rows
+    /// ## Arguments
+    /// * `other` - Table to compare with
+    /// ## Returns
+    /// [`bool`] `true` if both tables have the same column names with the same types in the same order
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("a", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("b", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("c", vec![TableRow::new("id", TypeDefs::U64)]).unwrap();
+    /// db.table("a").unwrap().insert(vec![1_i64.into()]).unwrap();
+    ///
+    /// let a = db.table("a").unwrap().clone();
+    /// assert!(db.table("b").unwrap().schemas_equal(&a));
+    /// assert!(!db.table("c").unwrap().schemas_equal(&a));
+    /// ```
+    pub fn schemas_equal(&self, other: &Table) -> bool {
+        self.headers.len() == other.headers.len()
+            && self
+                .headers
+                .iter()
+                .zip(other.headers.iter())
+                .all(|(a, b)| a.key == b.key && a.rtype == b.rtype)
+    }
+
+    /// Copy all rows to another table with the same schema
+    /// ## Arguments
+    /// * `target` - Table to copy rows into, must have same column names in same order with same types
+    /// ## Returns
+    /// * [`Ok<usize>`] Copied row count
+    /// * [`Err<CopyError>`] If schemas are not compatible or a copied row breaks a unique constraint of target
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// for name in ["users", "users_backup"] {
+    ///     db.create_table(name, vec![
+    ///        TableRow::new("name", TypeDefs::String),
+    ///     ]).unwrap();
+    /// }
+    /// db.table("users").unwrap().insert(vec!["John".into()]).unwrap();
+    /// let users = db.table("users").unwrap().clone();
+    /// assert_eq!(users.copy_to(db.table("users_backup").unwrap()), Ok(1));
+    /// assert_eq!(db.table("users_backup").unwrap().get_all().len(), 1);
+    /// ```
+    pub fn copy_to(&self, target: &mut Table) -> Result<usize, CopyError> {
+        if !self.schemas_equal(target) {
+            if self.headers.len() != target.headers.len() {
+                return Err(CopyError::ColumnCountMismatch {
+                    expected: target.headers.len(),
+                    got: self.headers.len(),
+                });
+            }
+            for (header, target_header) in self.headers.iter().zip(target.headers.iter()) {
+                if header.key != target_header.key {
+                    return Err(CopyError::ColumnNameMismatch {
+                        expected: target_header.key.clone(),
+                        got: header.key.clone(),
+                    });
+                }
+                if header.rtype != target_header.rtype {
+                    return Err(CopyError::SchemaMismatch {
+                        column: header.key.clone(),
+                        expected: target_header.rtype.clone(),
+                        got: header.rtype.clone(),
+                    });
+                }
+            }
+        }
+
+        let row_count = target.columns.len();
+        let mut unique_values = target.take_unique_values();
+        if let Some(sets) = &mut unique_values {
+            let rows = self
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(offset, row)| (row_count + offset, row.as_slice()));
+            if let Err((_, row)) = Table::add_unique_values(sets, rows) {
+                return Err(CopyError::UniqueViolation { row });
+            }
+        }
+        target.invalidate_cache();
+        target.columns.extend(self.columns.iter().cloned());
+        target.keep_unique_values(unique_values);
+        #[cfg(feature = "events")]
+        for row in self.columns.iter() {
+            target.emit(EventType::Insert, row);
+        }
+        Ok(self.columns.len())
+    }
+
+    /// New table with the schema of this table and given rows, fails if `other` has another schema
+    fn set_op_result(
+        &self,
+        other: &Table,
+        columns: Vec<Vec<SafeType>>,
+    ) -> Result<Table, SetOpError> {
+        if !self.schemas_equal(other) {
+            return Err(SetOpError::SchemaMismatch);
+        }
+        let mut table = Table::new(&self.name, self.headers.clone());
+        table.columns = columns;
+        Ok(table)
+    }
+
+    /// Rows of both tables, duplicates are kept
+    /// ## Arguments
+    /// * `other` - Table with the same schema
+    /// ## Returns
+    /// * [`Ok<Table>`] New table with rows of this table followed by rows of `other`
+    /// * [`Err<SetOpError>`] If schemas differ
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("a", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("b", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.table("a").unwrap().insert(vec![1_i64.into()]).unwrap();
+    /// db.table("b").unwrap().insert(vec![1_i64.into()]).unwrap();
+    /// db.table("b").unwrap().insert(vec![2_i64.into()]).unwrap();
+    ///
+    /// let b = db.table("b").unwrap().clone();
+    /// assert_eq!(db.table("a").unwrap().union(&b).unwrap().get_all().len(), 3);
+    /// ```
+    pub fn union(&self, other: &Table) -> Result<Table, SetOpError> {
+        let mut columns = self.columns.clone();
+        columns.extend(other.columns.iter().cloned());
+        self.set_op_result(other, columns)
+    }
+
+    /// Rows of this table that are also present in `other`
+    /// ## Arguments
+    /// * `other` - Table with the same schema
+    /// ## Returns
+    /// * [`Ok<Table>`] New table with the common rows
+    /// * [`Err<SetOpError>`] If schemas differ
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{SetOpError, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("a", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("b", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("c", vec![TableRow::new("name", TypeDefs::String)]).unwrap();
+    /// for id in [1_i64, 2, 3] {
+    ///     db.table("a").unwrap().insert(vec![id.into()]).unwrap();
+    /// }
+    /// db.table("b").unwrap().insert(vec![2_i64.into()]).unwrap();
+    ///
+    /// let b = db.table("b").unwrap().clone();
+    /// let common = db.table("a").unwrap().intersect(&b).unwrap();
+    /// assert_eq!(common.get_all()[0].get("id").unwrap().get::<i64>(), 2);
+    ///
+    /// let c = db.table("c").unwrap().clone();
+    /// assert_eq!(db.table("a").unwrap().intersect(&c).err(), Some(SetOpError::SchemaMismatch));
+    /// ```
+    pub fn intersect(&self, other: &Table) -> Result<Table, SetOpError> {
+        let columns = self
+            .columns
+            .iter()
+            .filter(|row| other.contains(row))
+            .cloned()
+            .collect();
+        self.set_op_result(other, columns)
+    }
+
+    /// Rows of this table that are not present in `other`, same as [`Table::except`]
+    /// ## Arguments
+    /// * `other` - Table with the same schema
+    /// ## Returns
+    /// * [`Ok<Table>`] New table without the rows of `other`
+    /// * [`Err<SetOpError>`] If schemas differ
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("a", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("b", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// for id in [1_i64, 2, 3] {
+    ///     db.table("a").unwrap().insert(vec![id.into()]).unwrap();
+    /// }
+    /// db.table("b").unwrap().insert(vec![2_i64.into()]).unwrap();
+    ///
+    /// let b = db.table("b").unwrap().clone();
+    /// assert_eq!(db.table("a").unwrap().subtract(&b).unwrap().get_all().len(), 2);
+    /// ```
+    pub fn subtract(&self, other: &Table) -> Result<Table, SetOpError> {
+        self.except(other)
+    }
+
+    /// Rows of this table that are not present in `other`, like SQL's `EXCEPT`
+    ///
+    /// Unlike SQL, duplicate rows of this table are kept unless `other` has the row
+    /// ## Arguments
+    /// * `other` - Table with the same schema
+    /// ## Returns
+    /// * [`Ok<Table>`] New table without the rows of `other`
+    /// * [`Err<SetOpError>`] If schemas differ
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{SetOpError, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("a", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("b", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("c", vec![TableRow::new("id", TypeDefs::U64)]).unwrap();
+    /// for id in [1_i64, 1, 2, 3] {
+    ///     db.table("a").unwrap().insert(vec![id.into()]).unwrap();
+    /// }
+    /// db.table("b").unwrap().insert(vec![2_i64.into()]).unwrap();
+    ///
+    /// let b = db.table("b").unwrap().clone();
+    /// let ids: Vec<i64> = db.table("a").unwrap().except(&b).unwrap()
+    ///     .get_all().iter().map(|x| x.get("id").unwrap().get()).collect();
+    /// assert_eq!(ids, vec![1, 1, 3]);
+    ///
+    /// let c = db.table("c").unwrap().clone();
+    /// assert_eq!(db.table("a").unwrap().except(&c).err(), Some(SetOpError::SchemaMismatch));
+    /// ```
+    pub fn except(&self, other: &Table) -> Result<Table, SetOpError> {
+        let columns = self
+            .columns
+            .iter()
+            .filter(|row| !other.contains(row))
+            .cloned()
+            .collect();
+        self.set_op_result(other, columns)
+    }
+
+    /// Compute row by row differences to `other`, rows are compared by position
+    /// ## Arguments
+    /// * `other` - Table with the same schema
+    /// ## Returns
+    /// * [`Ok<TableDiff>`] Changes turning this table into `other`, apply with [`Table::apply_diff`]
+    /// * [`Err<SetOpError>`] If schemas differ
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("a", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.create_table("b", vec![TableRow::new("id", TypeDefs::I64)]).unwrap();
+    /// db.table("a").unwrap().insert(vec![1_i64.into()]).unwrap();
+    /// db.table("a").unwrap().insert(vec![2_i64.into()]).unwrap();
+    /// db.table("b").unwrap().insert(vec![1_i64.into()]).unwrap();
+    /// db.table("b").unwrap().insert(vec![3_i64.into()]).unwrap();
+    /// db.table("b").unwrap().insert(vec![4_i64.into()]).unwrap();
+    ///
+    /// let b = db.table("b").unwrap().clone();
+    /// let diff = db.table("a").unwrap().diff(&b).unwrap();
+    /// assert_eq!(diff.modified_rows.len(), 1);
+    /// assert_eq!(diff.added_rows.len(), 1);
+    /// assert!(diff.removed_rows.is_empty());
+    /// ```
+    pub fn diff(&self, other: &Table) -> Result<TableDiff, SetOpError> {
+        if !self.schemas_equal(other) {
+            return Err(SetOpError::SchemaMismatch);
+        }
+        let mut diff = TableDiff::default();
+        for (index, row) in self.columns.iter().enumerate() {
+            match other.columns.get(index) {
+                Some(other_row) if other_row != row => {
+                    diff.modified_rows.push((index, other_row.clone()))
+                }
+                Some(_) => (),
+                None => diff.removed_rows.push(index),
+            }
+        }
+        diff.added_rows = other
+            .columns
+            .iter()
+            .skip(self.columns.len())
+            .cloned()
+            .collect();
+        Ok(diff)
+    }
+
+    /// Combine rows at the same position of two tables into a new table
+    ///
+    /// Schema of the new table is taken from the first merged row, columns are named `column_0`, `column_1`, ...
+    /// ## Arguments
+    /// * `other` - Table with the same row count
+    /// * `on` - Merge function receiving the left and right rows and returning values of the new row
+    /// ## Returns
+    /// * [`Ok<Table>`] New table with one merged row per row of this table
+    /// * [`Err<ZipError>`] If row counts differ or a merged row does not match the first one
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, ZipError}};
+    /// let mut db = Database::new();
+    /// db.create_table("names", vec![TableRow::new("name", TypeDefs::String)]).unwrap();
+    /// db.create_table("scores", vec![TableRow::new("score", TypeDefs::I64)]).unwrap();
+    /// for (name, score) in [("Ali", 10_i64), ("Cem", 20)] {
+    ///     db.table("names").unwrap().insert(vec![name.into()]).unwrap();
+    ///     db.table("scores").unwrap().insert(vec![score.into()]).unwrap();
+    /// }
+    ///
+    /// let scores = db.table("scores").unwrap().clone();
+    /// let zipped = db.table("names").unwrap().zip_with(&scores, |left, right| {
+    ///     vec![left.get("name").unwrap().value.clone(), right.get("score").unwrap().value.clone()]
+    /// }).unwrap();
+    /// assert_eq!(zipped.get_headers()[1].key, "column_1");
+    /// assert_eq!(zipped.get_headers()[1].rtype, TypeDefs::I64);
+    /// assert_eq!(zipped.get_at(1).unwrap().get("column_0").unwrap().get::<String>(), "Cem");
+    ///
+    /// db.table("scores").unwrap().insert(vec![30_i64.into()]).unwrap();
+    /// let scores = db.table("scores").unwrap().clone();
+    /// assert_eq!(
+    ///     db.table("names").unwrap().zip_with(&scores, |left, _| vec![left.get("name").unwrap().value.clone()]).err(),
+    ///     Some(ZipError::LengthMismatch { left: 2, right: 3 })
+    /// );
+    /// ```
+    pub fn zip_with(
+        &self,
+        other: &Table,
+        on: impl Fn(&Entries, &Entries) -> Vec<SafeType>,
+    ) -> Result<Table, ZipError> {
+        if self.columns.len() != other.columns.len() {
+            return Err(ZipError::LengthMismatch {
+                left: self.columns.len(),
+                right: other.columns.len(),
+            });
+        }
+        let mut rows: Vec<Vec<SafeType>> = vec![];
+        for index in 0..self.columns.len() {
+            let (left, right) = match (self.row_at(index), other.row_at(index)) {
+                (Some(left), Some(right)) => (left, right),
+                _ => continue,
+            };
+            let row = on(&left, &right);
+            if let Some(first) = rows.first() {
+                if first.len() != row.len()
+                    || first
+                        .iter()
+                        .zip(row.iter())
+                        .any(|(a, b)| a.get_type_def() != b.get_type_def())
+                {
+                    return Err(ZipError::RowMismatch { row: index });
+                }
+            }
+            rows.push(row);
+        }
+        let headers = match rows.first() {
+            Some(first) => first
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    TableRow::new(&format!("column_{}", index), value.get_type_def())
+                })
+                .collect(),
+            None => vec![],
+        };
+        let mut table = Table::new(&self.name, headers);
+        table.columns = rows;
+        Ok(table)
+    }
+
+    /// Spread key and value columns of a long table into one column per key, the inverse of [`Table::pivot_longer`]
+    ///
+    /// Result has `id_col` followed by a column for every distinct value of `key_col`, in order of first appearance.
+    /// Key columns are `Option` of the value type, holding `None` for ids without that key
+    /// ## Arguments
+    /// * `id_col` - Column identifying the rows of the result
+    /// * `key_col` - Column whose values become column names
+    /// * `value_col` - Column whose values fill the new columns
+    /// ## Returns
+    /// * [`Ok<Table>`] New wide table with one row per distinct id
+    /// * [`Err<Vec<String>>`] If a column does not exist, a key names an existing column or an id has the same key twice
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("readings", vec![
+    ///    TableRow::new("city", TypeDefs::String),
+    ///    TableRow::new("month", TypeDefs::String),
+    ///    TableRow::new("rain", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("readings").unwrap();
+    /// table.insert(vec!["Izmir".into(), "jan".into(), 120_i64.into()]).unwrap();
+    /// table.insert(vec!["Izmir".into(), "feb".into(), 90_i64.into()]).unwrap();
+    /// table.insert(vec!["Van".into(), "jan".into(), 40_i64.into()]).unwrap();
+    ///
+    /// let wide = table.pivot_wider("city", "month", "rain").unwrap();
+    /// let headers: Vec<String> = wide.get_headers().into_iter().map(|x| x.key).collect();
+    /// assert_eq!(headers, vec!["city", "jan", "feb"]);
+    /// assert_eq!(wide.get_headers()[1].rtype, TypeDefs::option_of(TypeDefs::I64));
+    /// assert_eq!(wide.get_at(1).unwrap().get("feb").unwrap().value.get_type(), Types::Option(None));
+    /// ```
+    pub fn pivot_wider(
+        &self,
+        id_col: &str,
+        key_col: &str,
+        value_col: &str,
+    ) -> Result<Table, Vec<String>> {
+        let mut errors = vec![];
+        let mut positions = vec![];
+        for key in [id_col, key_col, value_col] {
+            match self.headers.iter().position(|x| x.key == key) {
+                Some(e) => positions.push(e),
+                None => errors.push(format!("Could not find key '{}' in table", key)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let (id_position, key_position, value_position) =
+            (positions[0], positions[1], positions[2]);
+
+        let mut keys: Vec<String> = vec![];
+        let mut ids: Vec<SafeType> = vec![];
+        let mut id_rows: HashMap<Vec<u8>, usize> = HashMap::new();
+        let mut cells: HashMap<(usize, usize), SafeType> = HashMap::new();
+        for row in &self.columns {
+            let key = csv_value(&row[key_position].rtype);
+            let key_index = match keys.iter().position(|x| *x == key) {
+                Some(e) => e,
+                None => {
+                    if key == id_col {
+                        errors.push(format!("Key '{}' is the name of the id column", key));
+                    }
+                    keys.push(key.clone());
+                    keys.len() - 1
+                }
+            };
+            let id = &row[id_position];
+            let id_index = *id_rows.entry(utils::value_bytes(id)).or_insert_with(|| {
+                ids.push(id.clone());
+                ids.len() - 1
+            });
+            if cells
+                .insert((id_index, key_index), row[value_position].clone())
+                .is_some()
+            {
+                errors.push(format!(
+                    "Id {} has more than one value for key '{}'",
+                    id.rtype, key
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let value_type = self.headers[value_position].rtype.clone();
+        let mut headers = vec![self.headers[id_position].clone()];
+        headers.extend(
+            keys.iter()
+                .map(|key| TableRow::new(key, TypeDefs::option_of(value_type.clone()))),
+        );
+        let mut table = Table::new(&self.name, headers);
+        table.columns = ids
+            .into_iter()
+            .enumerate()
+            .map(|(id_index, id)| {
+                let mut row = vec![id];
+                row.extend((0..keys.len()).map(|key_index| {
+                    match cells.remove(&(id_index, key_index)) {
+                        Some(value) => SafeType::some(value),
+                        None => SafeType::none(value_type.clone()),
+                    }
+                }));
+                row
+            })
+            .collect();
+        Ok(table)
+    }
+
+    /// Gather measure columns of a wide table into name and value columns, the inverse of [`Table::pivot_wider`]
+    ///
+    /// Every row gives one row per measure column, holding the `id_cols`, the measure column name and its value
+    /// ## Arguments
+    /// * `id_cols` - Columns copied to every resulting row
+    /// * `measure_cols` - Columns to gather, all must have the same type
+    /// * `names_to` - Name of the new string column holding measure column names
+    /// * `values_to` - Name of the new column holding measure values
+    /// ## Returns
+    /// * [`Ok<Table>`] New long table
+    /// * [`Err<Vec<String>>`] If a column does not exist, measure columns are missing or have different types,
+    ///   or result column names repeat
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("rain", vec![
+    ///    TableRow::new("city", TypeDefs::String),
+    ///    TableRow::new("jan", TypeDefs::I64),
+    ///    TableRow::new("feb", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("rain").unwrap();
+    /// table.insert(vec!["Izmir".into(), 120_i64.into(), 90_i64.into()]).unwrap();
+    /// table.insert(vec!["Van".into(), 40_i64.into(), 35_i64.into()]).unwrap();
+    ///
+    /// let long = table.pivot_longer(&["city"], &["jan", "feb"], "month", "rain").unwrap();
+    /// assert_eq!(long.get_row_count(), 4);
+    /// let row = long.get_at(1).unwrap();
+    /// assert_eq!(row.get("month").unwrap().get::<String>(), "feb");
+    /// assert_eq!(row.get("rain").unwrap().get::<i64>(), 90);
+    /// assert!(table.pivot_longer(&["city"], &["jan", "city"], "month", "rain").is_err());
+    /// ```
+    pub fn pivot_longer(
+        &self,
+        id_cols: &[&str],
+        measure_cols: &[&str],
+        names_to: &str,
+        values_to: &str,
+    ) -> Result<Table, Vec<String>> {
+        let mut errors = vec![];
+        let mut position_of = |key: &str| match self.headers.iter().position(|x| x.key == key) {
+            Some(e) => Some(e),
+            None => {
+                errors.push(format!("Could not find key '{}' in table", key));
+                None
+            }
+        };
+        let id_positions: Vec<usize> = id_cols.iter().filter_map(|x| position_of(x)).collect();
+        let measure_positions: Vec<usize> =
+            measure_cols.iter().filter_map(|x| position_of(x)).collect();
+        if measure_cols.is_empty() {
+            errors.push("No measure columns given".to_string());
+        }
+        if let Some(first) = measure_positions.first() {
+            let rtype = &self.headers[*first].rtype;
+            for position in &measure_positions {
+                if &self.headers[*position].rtype != rtype {
+                    errors.push(format!(
+                        "Measure column '{}' has type {}, expected {}",
+                        self.headers[*position].key, self.headers[*position].rtype, rtype
+                    ));
+                }
+            }
+        }
+        let mut names: Vec<&str> = id_cols.to_vec();
+        names.extend([names_to, values_to]);
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                errors.push(format!(
+                    "Column '{}' appears more than once in result",
+                    name
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut headers: Vec<TableRow> = id_positions
+            .iter()
+            .map(|position| self.headers[*position].clone())
+            .collect();
+        headers.push(TableRow::new(names_to, TypeDefs::String));
+        headers.push(TableRow::new(
+            values_to,
+            self.headers[measure_positions[0]].rtype.clone(),
+        ));
+        let mut table = Table::new(&self.name, headers);
+        for row in &self.columns {
+            for position in &measure_positions {
+                let mut long_row: Vec<SafeType> =
+                    id_positions.iter().map(|x| row[*x].clone()).collect();
+                long_row.push(self.headers[*position].key.clone().into());
+                long_row.push(row[*position].clone());
+                table.columns.push(long_row);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Group rows by a column, storing the other columns of each group as an array of tuples
+    ///
+    /// Result has the `group_by_column` and a `rows` column of `Array(Tuple(..))`, groups are in order of first appearance.
+    /// Names of the nested columns are kept in the `nested_columns` metadata of `rows` so [`Table::unnest`] can restore them
+    /// ## Arguments
+    /// * `group_by_column` - Column whose distinct values become the rows of the result
+    /// ## Returns
+    /// * [`Ok<Table>`] New table with one row per group
+    /// * [`Err<Vec<String>>`] If column does not exist, is named `rows` or more than 255 columns would be nested
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("orders", vec![
+    ///    TableRow::new("customer", TypeDefs::String),
+    ///    TableRow::new("item", TypeDefs::String),
+    ///    TableRow::new("price", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("orders").unwrap();
+    /// table.insert(vec!["Ali".into(), "pen".into(), 5_i64.into()]).unwrap();
+    /// table.insert(vec!["Cem".into(), "book".into(), 20_i64.into()]).unwrap();
+    /// table.insert(vec!["Ali".into(), "bag".into(), 40_i64.into()]).unwrap();
+    ///
+    /// let nested = table.nest("customer").unwrap();
+    /// assert_eq!(nested.get_row_count(), 2);
+    /// assert_eq!(
+    ///     nested.get_headers()[1].rtype,
+    ///     TypeDefs::array_of(TypeDefs::Tuple(vec![TypeDefs::String, TypeDefs::I64]))
+    /// );
+    /// assert_eq!(nested.get_where(|x| x.row("customer").is("Ali"))[0].row("rows").size(), 2);
+    /// ```
+    pub fn nest(&self, group_by_column: &str) -> Result<Table, Vec<String>> {
+        let position = match self.headers.iter().position(|x| x.key == group_by_column) {
+            Some(e) => e,
+            None => {
+                return Err(vec![format!(
+                    "Could not find key '{}' in table",
+                    group_by_column
+                )])
+            }
+        };
+        if group_by_column == "rows" {
+            return Err(vec![
+                "Column 'rows' is used for nested rows and can not be grouped by".to_string(),
+            ]);
+        }
+        let inner_positions: Vec<usize> =
+            (0..self.headers.len()).filter(|x| *x != position).collect();
+        if inner_positions.len() > u8::MAX as usize {
+            return Err(vec![format!(
+                "Can not nest more than {} columns into a tuple",
+                u8::MAX
+            )]);
+        }
+        let tuple_type = TypeDefs::Tuple(
+            inner_positions
+                .iter()
+                .map(|x| self.headers[*x].rtype.clone())
+                .collect(),
+        );
+
+        let mut keys: Vec<SafeType> = vec![];
+        let mut groups: Vec<Vec<SafeType>> = vec![];
+        let mut group_of: HashMap<Vec<u8>, usize> = HashMap::new();
+        for row in &self.columns {
+            let group = *group_of
+                .entry(utils::value_bytes(&row[position]))
+                .or_insert_with(|| {
+                    keys.push(row[position].clone());
+                    groups.push(vec![]);
+                    groups.len() - 1
+                });
+            groups[group].push(SafeType::tuple(
+                inner_positions.iter().map(|x| row[*x].clone()).collect(),
+            ));
+        }
+
+        let mut table = Table::new(
+            &self.name,
+            vec![
+                self.headers[position].clone(),
+                TableRow::new("rows", TypeDefs::array_of(tuple_type.clone())),
+            ],
+        );
+        table.columns = keys
+            .into_iter()
+            .zip(groups)
+            .map(|(key, rows)| {
+                vec![
+                    key,
+                    SafeType::build(Types::Array(rows), TypeDefs::array_of(tuple_type.clone())),
+                ]
+            })
+            .collect();
+        let names: Vec<&str> = inner_positions
+            .iter()
+            .map(|x| self.headers[*x].key.as_str())
+            .collect();
+        table.column_metadata.insert(
+            "rows".to_string(),
+            HashMap::from([("nested_columns".to_string(), names.join(","))]),
+        );
+        Ok(table)
+    }
+
+    /// Expand an array column into one row per array element, the inverse of [`Table::nest`]
+    ///
+    /// Tuple elements are spread over one column per field, named from the `nested_columns` metadata set by
+    /// [`Table::nest`] or `{array_column}_{index}` without it. Other elements keep `array_column` as column name.
+    /// Rows with an empty array are dropped
+    /// ## Arguments
+    /// * `array_column` - Name of the array column
+    /// ## Returns
+    /// * [`Ok<Table>`] New flat table
+    /// * [`Err<Vec<String>>`] If column does not exist, is not an array or expanded column names repeat
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("orders", vec![
+    ///    TableRow::new("customer", TypeDefs::String),
+    ///    TableRow::new("price", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("orders").unwrap();
+    /// table.insert(vec!["Ali".into(), 5_i64.into()]).unwrap();
+    /// table.insert(vec!["Cem".into(), 20_i64.into()]).unwrap();
+    /// table.insert(vec!["Ali".into(), 40_i64.into()]).unwrap();
+    ///
+    /// let flat = table.nest("customer").unwrap().unnest("rows").unwrap();
+    /// let headers: Vec<String> = flat.get_headers().into_iter().map(|x| x.key).collect();
+    /// assert_eq!(headers, vec!["customer", "price"]);
+    /// assert_eq!(flat.get_row_count(), 3);
+    /// assert_eq!(flat.get_at(1).unwrap().get("price").unwrap().get::<i64>(), 40);
+    /// assert!(table.unnest("price").is_err());
+    /// ```
+    pub fn unnest(&self, array_column: &str) -> Result<Table, Vec<String>> {
+        let position = match self.headers.iter().position(|x| x.key == array_column) {
+            Some(e) => e,
+            None => {
+                return Err(vec![format!(
+                    "Could not find key '{}' in table",
+                    array_column
+                )])
+            }
+        };
+        let inner = match &self.headers[position].rtype {
+            TypeDefs::Array(inner) => *inner.clone(),
+            rtype => {
+                return Err(vec![format!(
+                    "Column '{}' is not an array, got {}",
+                    array_column, rtype
+                )])
+            }
+        };
+        let nested_headers: Vec<TableRow> = match &inner {
+            TypeDefs::Tuple(types) => {
+                let stored: Option<Vec<String>> = self
+                    .column_metadata
+                    .get(array_column)
+                    .and_then(|x| x.get("nested_columns"))
+                    .map(|x| x.split(',').map(|x| x.to_string()).collect())
+                    .filter(|x: &Vec<String>| x.len() == types.len());
+                types
+                    .iter()
+                    .enumerate()
+                    .map(|(index, rtype)| {
+                        let name = match &stored {
+                            Some(names) => names[index].clone(),
+                            None => format!("{}_{}", array_column, index),
+                        };
+                        TableRow::new(&name, rtype.clone())
+                    })
+                    .collect()
+            }
+            rtype => vec![TableRow::new(array_column, rtype.clone())],
+        };
+
+        let mut headers = self.headers[..position].to_vec();
+        headers.extend(nested_headers);
+        headers.extend(self.headers[position + 1..].iter().cloned());
+        let mut errors = vec![];
+        for (index, header) in headers.iter().enumerate() {
+            if headers[..index].iter().any(|x| x.key == header.key) {
+                errors.push(format!(
+                    "Column '{}' appears more than once in result",
+                    header.key
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut table = Table::new(&self.name, headers);
+        for row in &self.columns {
+            let elements = match row[position].get_type() {
+                Types::Array(e) => e,
+                _ => continue,
+            };
+            for element in elements {
+                let mut flat_row = row[..position].to_vec();
+                match element.rtype {
+                    Types::Tuple(fields) if matches!(inner, TypeDefs::Tuple(_)) => {
+                        flat_row.extend(fields)
+                    }
+                    _ => flat_row.push(element),
+                }
+                flat_row.extend(row[position + 1..].iter().cloned());
+                table.columns.push(flat_row);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Apply changes computed by [`Table::diff`]
+    ///
+    /// Row indexes refer to the table before any change, rows are updated first, then removed,
+    /// and added rows are appended last. Modified and added rows are checked like [`Table::insert`]
+    /// before the table is touched, and all changes are undone if they break a unique constraint
+    /// ## Arguments
+    /// * `diff` - Changes to apply
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<Vec<String>>`] If an index is out of bounds, a row does not match the schema or the changes break a unique constraint
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableDiff, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// let rows = vec![TableRow::new("name", TypeDefs::String)];
+    /// db.create_table("primary", rows.clone()).unwrap();
+    /// db.create_table("replica", rows).unwrap();
+    /// for name in ["Ali", "Cem"] {
+    ///     db.table("primary").unwrap().insert(vec![name.into()]).unwrap();
+    ///     db.table("replica").unwrap().insert(vec![name.into()]).unwrap();
+    /// }
+    /// db.table("primary").unwrap().set_column_value_where(|x| x.row("name").is("Ali"), "name", "Alp".into()).unwrap();
+    /// db.table("primary").unwrap().insert(vec!["Bora".into()]).unwrap();
+    ///
+    /// let primary = db.table("primary").unwrap().clone();
+    /// let diff = db.table("replica").unwrap().diff(&primary).unwrap();
+    /// db.table("replica").unwrap().apply_diff(&diff).unwrap();
+    /// assert_eq!(db.table("replica").unwrap().to_csv_string(), primary.to_csv_string());
+    ///
+    /// let invalid = TableDiff { removed_rows: vec![10], ..Default::default() };
+    /// assert!(db.table("replica").unwrap().apply_diff(&invalid).is_err());
+    ///
+    /// db.build_table("names").column("name", TypeDefs::String).unique("name").finish().unwrap();
+    /// db.table("names").unwrap().insert(vec!["Ali".into()]).unwrap();
+    /// let duplicate = TableDiff { added_rows: vec![vec!["Ali".into()]], ..Default::default() };
+    /// assert!(db.table("names").unwrap().apply_diff(&duplicate).is_err());
+    /// assert_eq!(db.table("names").unwrap().get_all().len(), 1);
+    /// ```
+    pub fn apply_diff(&mut self, diff: &TableDiff) -> Result<(), Vec<String>> {
+        let mut errors = vec![];
+        let row_count = self.columns.len();
+        let mut modified_rows = Vec::with_capacity(diff.modified_rows.len());
+        for (index, row) in &diff.modified_rows {
+            if *index >= row_count {
+                errors.push(format!("Modified row {} is out of bounds", index));
+            }
+            match self.validate_row(row.clone()) {
+                Ok(row) => modified_rows.push((*index, row)),
+                Err(row_errors) => errors.extend(row_errors),
+            }
+        }
+        for index in &diff.removed_rows {
+            if *index >= row_count {
+                errors.push(format!("Removed row {} is out of bounds", index));
+            }
+        }
+        let mut added_rows = Vec::with_capacity(diff.added_rows.len());
+        for row in &diff.added_rows {
+            match self.validate_row(row.clone()) {
+                Ok(row) => added_rows.push(row),
+                Err(row_errors) => errors.extend(row_errors),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut removed_rows = diff.removed_rows.clone();
+        removed_rows.sort_unstable();
+        removed_rows.dedup();
+        let mut unique_values = self.take_unique_values();
+        if let Some(sets) = &mut unique_values {
+            // Last change of a row wins, rows that are removed anyway are not checked
+            let changed: BTreeMap<usize, &[SafeType]> = modified_rows
+                .iter()
+                .filter(|(index, _)| removed_rows.binary_search(index).is_err())
+                .map(|(index, row)| (*index, row.as_slice()))
+                .collect();
+            let replaced = changed
+                .keys()
+                .chain(removed_rows.iter())
+                .map(|index| self.columns[*index].as_slice());
+            Table::remove_unique_values(sets, replaced);
+            let kept_count = row_count - removed_rows.len();
+            let rows = changed
+                .into_iter()
+                .map(|(index, row)| (index - removed_rows.partition_point(|x| *x < index), row))
+                .chain(
+                    added_rows
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, row)| (kept_count + offset, row.as_slice())),
+                );
+            Table::add_unique_values(sets, rows)
+                .map_err(|violation| self.unique_error(violation))?;
+        }
+
+        self.invalidate_cache();
+        for (index, row) in &modified_rows {
+            self.columns[*index] = row.clone();
+        }
+        #[cfg(feature = "events")]
+        let mut removed = vec![];
+        for index in removed_rows.into_iter().rev() {
+            let _removed = self.columns.remove(index);
+            #[cfg(feature = "events")]
+            removed.push(_removed);
+        }
+        self.columns.extend(added_rows.iter().cloned());
+        self.keep_unique_values(unique_values);
+        #[cfg(feature = "events")]
+        {
+            for (_, row) in &modified_rows {
+                self.emit(EventType::Update, row);
+            }
+            for row in removed {
+                self.emit(EventType::Delete, &row);
+            }
+            for row in &added_rows {
+                self.emit(EventType::Insert, row);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop every column that is not in the keep list, column order is preserved
+    /// ## Arguments
+    /// * `keep` - Names of the columns to keep
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<MigrationError>`] If a column in keep list does not exist, table is left untouched
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{MigrationError, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    ///    TableRow::new("city", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 12_i64.into(), "Izmir".into()]).unwrap();
+    /// table.retain_columns(&["city", "name"]).unwrap();
+    /// assert_eq!(table.get_headers().len(), 2);
+    /// assert_eq!(table.get_at(0).unwrap().get("city").unwrap().get::<String>(), "Izmir");
+    /// assert_eq!(
+    ///     table.retain_columns(&["age"]),
+    ///     Err(MigrationError::ColumnNotFound("age".to_string()))
+    /// );
+    /// ```
+    pub fn retain_columns(&mut self, keep: &[&str]) -> Result<(), MigrationError> {
+        if let Some(missing) = keep
+            .iter()
+            .find(|key| !self.headers.iter().any(|x| &x.key == *key))
+        {
+            return Err(MigrationError::ColumnNotFound(missing.to_string()));
+        }
+
+        self.invalidate_cache();
+        let kept: Vec<bool> = self
+            .headers
+            .iter()
+            .map(|x| keep.contains(&x.key.as_str()))
+            .collect();
+        self.column_metadata
+            .retain(|key, _| keep.contains(&key.as_str()));
+        self.unique_columns
+            .retain(|key| keep.contains(&key.as_str()));
+        self.column_defaults
+            .retain(|key, _| keep.contains(&key.as_str()));
+        self.lru_indexes
+            .retain(|key, _| keep.contains(&key.as_str()));
+        self.bloom_indexes
+            .lock()
+            .retain(|key, _| keep.contains(&key.as_str()));
+        let mut index = 0;
+        self.headers.retain(|_| {
+            index += 1;
+            kept[index - 1]
+        });
+        for column in self.columns.iter_mut() {
+            let mut index = 0;
+            column.retain(|_| {
+                index += 1;
+                kept[index - 1]
+            });
+        }
+        Ok(())
+    }
+
+    /// Compact row storage, dropping deleted rows
+    ///
+    /// Rows are removed from storage immediately, so there is nothing to compact yet.
+    /// This only releases unused capacity and always returns 0
+    /// ## Returns
+    /// [`usize`] Number of rows removed from storage
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into()]).unwrap();
+    /// assert_eq!(table.reindex(), 0);
+    /// assert_eq!(table.get_all().len(), 1);
+    /// ```
+    pub fn reindex(&mut self) -> usize {
+        self.columns.shrink_to_fit();
+        0
+    }
+
+    /// Get all columns as a vector of Entries
+    /// ## Returns
+    /// * [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{Entries, Entry, Types, TypeDefs, TableRow}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///   TableRow::new("name", TypeDefs::String),
+    ///   TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![
+    ///   "John".into(),
+    ///   12_i64.into()
+    /// ]);
+    /// let entries = db.table("users").unwrap().get_all();
+    /// ```
+    pub fn get_all(&self) -> Vec<Entries> {
+        let mut all = Vec::new();
+        for i in 0..self.columns.len() {
+            let mut entries = Vec::new();
+            for j in 0..self.columns[i].len() {
+                entries.push(Entry {
+                    key: self.headers[j].key.clone(),
+                    value: self.columns[i][j].clone(),
+                });
+            }
+            all.push(Entries { entries });
+        }
+        all
+    }
+
+    /// Get values of a column for the left side of a join lookup
+    ///
+    /// Missing values of `Option` columns are skipped and present ones are unwrapped
+    /// ## Arguments
+    /// * `column` - Name of the column
+    /// ## Returns
+    /// [`Vec<Types>`] Values in row order, empty if column does not exist
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{SafeType, TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("id", TypeDefs::I64),
+    ///     TableRow::new("manager_id", TypeDefs::option_of(TypeDefs::I64)),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec![1_i64.into(), SafeType::none(TypeDefs::I64)]).unwrap();
+    /// table.insert(vec![2_i64.into(), SafeType::some(1_i64.into())]).unwrap();
+    ///
+    /// assert_eq!(table.left_values("id"), vec![Types::I64(1), Types::I64(2)]);
+    /// assert_eq!(table.left_values("manager_id"), vec![Types::I64(1)]);
+    /// ```
+    pub fn left_values(&self, column: &str) -> Vec<Types> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return vec![],
+        };
+        self.columns
+            .iter()
+            .filter_map(|row| match row[position].get_type() {
+                Types::Option(value) => value.map(|x| x.get_type()),
+                value => Some(value),
+            })
+            .collect()
+    }
+
+    /// Get values of a column for the right side of a join lookup, same as [`Table::left_values`]
+    /// ## Arguments
+    /// * `column` - Name of the column
+    /// ## Returns
+    /// [`Vec<Types>`] Values in row order, empty if column does not exist
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("orders", vec![
+    ///     TableRow::new("user_id", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("orders").unwrap();
+    /// table.insert(vec![2_i64.into()]).unwrap();
+    /// assert_eq!(table.right_values("user_id"), vec![Types::I64(2)]);
+    /// assert!(table.right_values("id").is_empty());
+    /// ```
+    pub fn right_values(&self, column: &str) -> Vec<Types> {
+        self.left_values(column)
+    }
+
+    /// Get the first `n` rows, rows past `n` are never built
+    /// ## Arguments
+    /// * `n` - Maximum number of rows to return
+    /// ## Returns
+    /// * [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///   TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for age in 0..20_i64 {
+    ///     table.insert(vec![age.into()]).unwrap();
+    /// }
+    /// let first = table.take(10);
+    /// assert_eq!(first.len(), 10);
+    /// assert_eq!(first[9].get("age").unwrap().get::<i64>(), 9);
+    /// assert_eq!(table.take(50).len(), 20);
+    /// ```
+    pub fn take(&self, n: usize) -> Vec<Entries> {
+        self.iter().take(n).collect()
+    }
+
+    /// Get all rows, last inserted first
+    /// ## Returns
+    /// * [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("events", vec![
+    ///   TableRow::new("id", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("events").unwrap();
+    /// for id in 0..3_i64 {
+    ///     table.insert(vec![id.into()]).unwrap();
+    /// }
+    /// let ids: Vec<i64> = table.reverse().iter().map(|x| x.get("id").unwrap().get()).collect();
+    /// assert_eq!(ids, vec![2, 1, 0]);
+    /// ```
+    pub fn reverse(&self) -> Vec<Entries> {
+        (0..self.columns.len())
+            .rev()
+            .filter_map(|index| self.row_at(index))
+            .collect()
+    }
+
+    /// Get all rows starting from row `offset`, wrapping around to the first row
+    ///
+    /// Negative offsets count from the end, an offset of -1 starts from the last row
+    /// ## Arguments
+    /// * `offset` - Index of the row returned first
+    /// ## Returns
+    /// * [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("ring", vec![
+    ///   TableRow::new("id", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("ring").unwrap();
+    /// for id in 0..5_i64 {
+    ///     table.insert(vec![id.into()]).unwrap();
+    /// }
+    /// let ids = |rows: Vec<safe_en::table::Entries>| -> Vec<i64> {
+    ///     rows.iter().map(|x| x.get("id").unwrap().get()).collect()
+    /// };
+    /// assert_eq!(ids(table.rotate(3)), vec![3, 4, 0, 1, 2]);
+    /// assert_eq!(ids(table.rotate(-1)), vec![4, 0, 1, 2, 3]);
+    /// assert_eq!(ids(table.rotate(7)), vec![2, 3, 4, 0, 1]);
+    /// ```
+    pub fn rotate(&self, offset: i64) -> Vec<Entries> {
+        let mut rows = self.get_all();
+        if !rows.is_empty() {
+            let len = rows.len() as i128;
+            rows.rotate_left((offset as i128).rem_euclid(len) as usize);
+        }
+        rows
+    }
+
+    /// Skip the first `n` rows, combine with [`SkipTake::take`] and [`SkipTake::filter`] for paging
+    /// ## Arguments
+    /// * `n` - Number of rows to skip
+    /// ## Returns
+    /// * [`SkipTake`] view of the table
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///   TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for age in 0..200_i64 {
+    ///     table.insert(vec![age.into()]).unwrap();
+    /// }
+    /// let page = table.skip(100).take(50).get();
+    /// assert_eq!(page.len(), 50);
+    /// assert_eq!(page[0].get("age").unwrap().get::<i64>(), 100);
+    /// assert_eq!(table.skip(190).get().len(), 10);
+    /// ```
+    pub fn skip(&self, n: usize) -> SkipTake<'_> {
+        SkipTake {
+            table: self,
+            skip: n,
+            take: None,
+            filter: None,
+        }
+    }
+
+    /// Iterate all rows lazily
+    /// ## Returns
+    /// [`Rows`] Iterator of [`Entries`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///   TableRow::new("name", TypeDefs::String),
+    ///   TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 12_i64.into()]).unwrap();
+    /// table.insert(vec!["Jane".into(), 30_i64.into()]).unwrap();
+    /// let names: Vec<String> = table
+    ///     .iter()
+    ///     .filter(|x| x.get("age").unwrap().get::<i64>() > 18)
+    ///     .map(|x| x.get("name").unwrap().get())
+    ///     .collect();
+    /// assert_eq!(names, vec!["Jane".to_string()]);
+    /// ```
+    pub fn iter(&self) -> Rows<'_> {
+        Rows {
+            table: self,
+            index: 0,
+        }
+    }
+
+    /// Compute a checksum of table schema and rows, table name is not included
+    /// ## Returns
+    /// [`u64`] FNV-1a hash of the serialized headers and rows
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// for name in ["users", "users_backup"] {
+    ///     db.create_table(name, vec![
+    ///       TableRow::new("name", TypeDefs::String),
+    ///       TableRow::new("age", TypeDefs::I64),
+    ///     ]).unwrap();
+    ///     db.table(name).unwrap().insert(vec!["John".into(), 12_i64.into()]).unwrap();
+    /// }
+    /// let checksum = db.table("users").unwrap().compute_checksum();
+    /// assert_eq!(checksum, db.table("users_backup").unwrap().compute_checksum());
+    ///
+    /// db.table("users").unwrap().insert(vec!["Jane".into(), 30_i64.into()]).unwrap();
+    /// assert_ne!(checksum, db.table("users").unwrap().compute_checksum());
+    /// ```
+    pub fn compute_checksum(&self) -> u64 {
+        let mut bytes = vec![];
+
+        utils::extend_bytes_from_raw_type(
+            &mut bytes,
+            &utils::type_to_bytes(self.headers.len() as u64),
+        );
+
+        for header in self.headers.iter() {
+            utils::extend_bytes_from_raw_type(
+                &mut bytes,
+                &utils::type_to_bytes(header.key.clone()),
+            );
+            bytes.extend(header.rtype.get_base_and_second_layer());
+        }
+
+        utils::extend_bytes_from_raw_type(
+            &mut bytes,
+            &utils::type_to_bytes(self.columns.len() as u64),
+        );
+
+        for row in self.columns.iter() {
+            for data in row.iter() {
+                utils::extend_bytes_from_raw_type(
+                    &mut bytes,
+                    &utils::type_to_bytes(data.get_type()),
+                );
+            }
+        }
+
+        utils::fnv1a_64(&bytes)
+    }
+
+    /// Get row by index
+    /// ## Arguments
+    /// * `index` - Index of row
+    /// ## Returns
+    /// [`Option<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{Entries, Entry, Types, TypeDefs, TableRow}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().insert(vec![
+    ///     "John".into(),
+    ///     12_i64.into()
+    /// ]);
+    /// let entries = db.table("users").unwrap().get_at(0).unwrap();
+    /// ```
+    pub fn get_at(&self, index: usize) -> Option<Entries> {
+        match &self.row_cache {
+            Some(cache) => {
+                if let Some(entries) = cache.get(&index) {
+                    return Some(entries);
+                }
+                let entries = self.row_at(index)?;
+                cache.put(index, entries.clone());
+                Some(entries)
+            }
+            None => self.row_at(index),
+        }
+    }
+
+    /// Get rows in `start..end`, range is clamped to the rows of the table
+    /// ## Arguments
+    /// * `start` - Index of the first row
+    /// * `end` - Index after the last row
+    /// ## Returns
+    /// [`Vec<Entries>`] Rows in table order, empty if `start` is not before `end` or past the last row
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("id", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for id in 0..10_i64 {
+    ///     table.insert(vec![id.into()]).unwrap();
+    /// }
+    /// let page = table.get_range(4, 7);
+    /// assert_eq!(page.len(), 3);
+    /// assert_eq!(page[0].get("id").unwrap().get::<i64>(), 4);
+    /// assert_eq!(table.get_range(8, 20).len(), 2);
+    /// assert!(table.get_range(12, 20).is_empty());
+    /// ```
+    pub fn get_range(&self, start: usize, end: usize) -> Vec<Entries> {
+        let end = end.min(self.columns.len());
+        (start..end)
+            .filter_map(|index| self.row_at(index))
+            .collect()
+    }
+
+    /// Build row at index without touching the cache
+    pub(crate) fn row_at(&self, index: usize) -> Option<Entries> {
+        let column = self.columns.get(index)?;
+        let mut entries = Vec::new();
+        for i in 0..column.len() {
+            entries.push(Entry {
+                key: self.headers[i].key.clone(),
+                value: column[i].clone(),
+            });
+        }
+        Some(Entries { entries })
+    }
+
+    /// Remove row by index
+    /// ## Arguments
+    /// * `index` - Index of row
+    /// ## Returns
+    /// * [`Ok<Entries>`] Removed row
+    /// * [`Err<String>`] If index is out of bounds
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 12_i64.into()]).unwrap();
+    /// table.insert(vec!["Jane".into(), 14_i64.into()]).unwrap();
+    ///
+    /// let removed = table.remove_at(0).unwrap();
+    /// assert_eq!(removed.get("name").unwrap().get::<String>(), "John");
+    /// assert_eq!(table.get_at(0).unwrap().get("name").unwrap().get::<String>(), "Jane");
+    /// assert!(table.remove_at(1).is_err());
+    /// ```
+    pub fn remove_at(&mut self, index: usize) -> Result<Entries, String> {
+        let entries = match self.row_at(index) {
+            Some(e) => e,
+            None => {
+                return Err(format!(
+                    "Index {} is out of bounds for table with {} rows",
+                    index,
+                    self.columns.len()
+                ))
+            }
+        };
+        self.invalidate_cache();
+        let _removed = self.columns.remove(index);
+        #[cfg(feature = "events")]
+        self.emit(EventType::Delete, &_removed);
+        Ok(entries)
+    }
+
+    /// Remove all rows, columns of the table are kept
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into()]).unwrap();
+    /// table.insert(vec!["Jane".into()]).unwrap();
+    ///
+    /// table.truncate();
+    /// assert_eq!(table.get_row_count(), 0);
+    /// assert_eq!(table.get_headers().len(), 1);
+    /// ```
+    pub fn truncate(&mut self) {
+        self.invalidate_cache();
+        let _removed = core::mem::take(&mut self.columns);
+        #[cfg(feature = "events")]
+        for row in &_removed {
+            self.emit(EventType::Delete, row);
+        }
+    }
+
+    /// Get number of rows in table
+    /// ## Returns
+    /// [`usize`] Row count
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// assert_eq!(table.get_row_count(), 0);
+    /// table.insert(vec!["John".into()]).unwrap();
+    /// assert_eq!(table.get_row_count(), 1);
+    /// ```
+    pub fn get_row_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Remove a row by filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// [`usize`] Effected row length
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), 20_i64.into()]).unwrap();
+    /// table.insert(vec!["John".into(), 30_i64.into()]).unwrap();
+    /// table.insert(vec!["Ahmet".into(), 40_i64.into()]).unwrap();
+    /// table.insert(vec!["Jane".into(), 50_i64.into()]).unwrap();
+    ///
+    /// assert_eq!(table.remove_where(|entry| entry.row("name").is("Ahmet".to_string())), 2);
+    /// let rows = table.get_all();
+    /// assert_eq!(rows.len(), 2);
+    /// assert!(rows[0].row("name").is("John") && rows[1].row("name").is("Jane"));
+    /// ```
+    pub fn remove_where<E: Fn(Entries) -> bool + Clone + Sized>(&mut self, filter: E) -> usize {
+        self.invalidate_cache();
+        let mut matched = Vec::with_capacity(self.columns.len());
+        for entries in self.columns.iter() {
+            let fake_entries = Entries {
+                entries: entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, value)| Entry {
+                        key: self.headers[ix].key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+            };
+
+            matched.push(filter(fake_entries.clone()));
+        }
+
+        let mut removed = Vec::new();
+        let mut kept = Vec::with_capacity(self.columns.len());
+        for (row, is_match) in core::mem::take(&mut self.columns).into_iter().zip(matched) {
+            if is_match {
+                removed.push(row);
+            } else {
+                kept.push(row);
+            }
+        }
+        self.columns = kept;
+
+        #[cfg(feature = "events")]
+        for row in &removed {
+            self.emit(EventType::Delete, row);
+        }
+        removed.len()
+    }
+
+    /// Get the value of a column by filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `value` - Value to set
+    /// ## Returns
+    /// [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///   TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().get_where(|entry| entry.row("name").is("Ahmet".to_string()));
+    /// ```
+    pub fn get_where<E: Fn(Entries) -> bool + Clone + Sized>(&self, filter: E) -> Vec<Entries> {
+        let mut found_entries = Vec::new();
+        for entries in self.columns.iter() {
+            let fake_entries = Entries {
+                entries: entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, value)| Entry {
+                        key: self.headers[ix].key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+            };
+
+            if filter(fake_entries.clone()) {
+                found_entries.push(fake_entries);
+            }
+        }
+        found_entries
+    }
+
+    /// Get rows matching filter as maps of key and value, same as [`Entries::to_map`] on [`Table::get_where`] results
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// [`Vec<HashMap<String, Types>>`] Matching rows in table order
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs, Types};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["Mehmet".into(), 21_i64.into()]).unwrap();
+    ///
+    /// let maps = table.get_where_as_maps(|entry| entry.row("name").is("Mehmet".to_string()));
+    /// assert_eq!(maps.len(), 1);
+    /// assert_eq!(maps[0]["age"], Types::I64(21));
+    /// ```
+    pub fn get_where_as_maps<E: Fn(Entries) -> bool>(
+        &self,
+        filter: E,
+    ) -> Vec<HashMap<String, Types>> {
+        (0..self.columns.len())
+            .filter_map(|index| self.row_at(index))
+            .filter(|entries| filter(entries.clone()))
+            .map(|entries| {
+                entries
+                    .entries
+                    .into_iter()
+                    .map(|x| (x.key, x.value.rtype))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Count rows matching filter without collecting them
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// [`usize`] Number of matching rows
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["Mehmet".into(), 21_i64.into()]).unwrap();
+    /// table.insert(vec!["Ahmet".into(), 30_i64.into()]).unwrap();
+    /// assert_eq!(table.count_where(|entry| entry.row("name").is("Ahmet".to_string())), 2);
+    /// ```
+    pub fn count_where<E: Fn(Entries) -> bool>(&self, filter: E) -> usize {
+        (0..self.columns.len())
+            .filter_map(|i| self.row_at(i))
+            .filter(|e| filter(e.clone()))
+            .count()
+    }
+
+    /// Sum a numeric column over rows matching filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `column` - Name of the numeric column
+    /// ## Returns
+    /// * [`Ok<Types>`] Sum with the type of the column, zero if no row matches
+    /// * [`Err<String>`] If column does not exist, is not numeric or the sum overflows
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs, Types};
+    /// let mut db = Database::new();
+    /// db.create_table("orders", vec![
+    ///    TableRow::new("year", TypeDefs::I64),
+    ///    TableRow::new("total", TypeDefs::F64),
+    ///    TableRow::new("customer", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("orders").unwrap();
+    /// table.insert(vec![2023_i64.into(), 10.5_f64.into(), "Ali".into()]).unwrap();
+    /// table.insert(vec![2024_i64.into(), 20.0_f64.into(), "Cem".into()]).unwrap();
+    /// table.insert(vec![2024_i64.into(), 4.5_f64.into(), "Ali".into()]).unwrap();
+    ///
+    /// assert_eq!(table.sum_where(|x| x.row("year").is(2024_i64), "total"), Ok(Types::F64(24.5)));
+    /// assert_eq!(table.sum_where(|_| true, "year"), Ok(Types::I64(6071)));
+    /// assert!(table.sum_where(|_| true, "customer").is_err());
+    /// ```
+    pub fn sum_where<E: Fn(Entries) -> bool>(
+        &self,
+        filter: E,
+        column: &str,
+    ) -> Result<Types, String> {
+        let (rtype, values) = self.numeric_where(filter, column)?;
+        let mut sum = match rtype {
+            TypeDefs::I8 => Types::I8(0),
+            TypeDefs::I64 => Types::I64(0),
+            TypeDefs::U64 => Types::U64(0),
+            TypeDefs::F32 => Types::F32(0.),
+            _ => Types::F64(0.),
+        };
+        for value in values {
+            let overflow = || format!("Sum of column '{}' overflows", column);
+            sum = match (sum, value) {
+                (Types::I8(a), Types::I8(b)) => Types::I8(a.checked_add(b).ok_or_else(overflow)?),
+                (Types::I64(a), Types::I64(b)) => {
+                    Types::I64(a.checked_add(b).ok_or_else(overflow)?)
+                }
+                (Types::U64(a), Types::U64(b)) => {
+                    Types::U64(a.checked_add(b).ok_or_else(overflow)?)
+                }
+                (Types::F32(a), Types::F32(b)) => Types::F32(a + b),
+                (Types::F64(a), Types::F64(b)) => Types::F64(a + b),
+                (sum, _) => sum,
+            };
+        }
+        Ok(sum)
+    }
+
+    /// Average of a numeric column over rows matching filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `column` - Name of the numeric column
+    /// ## Returns
+    /// * [`Ok<Types>`] Average as [`Types::F64`] for every numeric column type
+    /// * [`Err<String>`] If column does not exist, is not numeric or no row matches
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs, Types};
+    /// let mut db = Database::new();
+    /// db.create_table("orders", vec![
+    ///    TableRow::new("quantity", TypeDefs::I64),
+    ///    TableRow::new("customer", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("orders").unwrap();
+    /// table.insert(vec![3_i64.into(), "Ali".into()]).unwrap();
+    /// table.insert(vec![4_i64.into(), "Ali".into()]).unwrap();
+    /// table.insert(vec![10_i64.into(), "Cem".into()]).unwrap();
+    ///
+    /// assert_eq!(table.avg_where(|x| x.row("customer").is("Ali"), "quantity"), Ok(Types::F64(3.5)));
+    /// assert!(table.avg_where(|x| x.row("customer").is("Bora"), "quantity").is_err());
+    /// assert!(table.avg_where(|_| true, "customer").is_err());
+    /// ```
+    pub fn avg_where<E: Fn(Entries) -> bool>(
+        &self,
+        filter: E,
+        column: &str,
+    ) -> Result<Types, String> {
+        let (_, values) = self.numeric_where(filter, column)?;
+        if values.is_empty() {
+            return Err(format!("No rows to average in column '{}'", column));
+        }
+        let count = values.len() as f64;
+        let sum: f64 = values
+            .into_iter()
+            .map(|value| match value {
+                Types::I8(e) => e as f64,
+                Types::I64(e) => e as f64,
+                Types::U64(e) => e as f64,
+                Types::F32(e) => e as f64,
+                Types::F64(e) => e,
+                _ => unreachable!(),
+            })
+            .sum();
+        Ok(Types::F64(sum / count))
+    }
+
+    /// Smallest value of a numeric column over rows matching filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `column` - Name of the numeric column
+    /// ## Returns
+    /// * [`Ok<Types>`] Smallest value with the type of the column
+    /// * [`Err<String>`] If column does not exist, is not numeric or no row matches
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs, Types};
+    /// let mut db = Database::new();
+    /// db.create_table("orders", vec![
+    ///    TableRow::new("quantity", TypeDefs::U64),
+    ///    TableRow::new("customer", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("orders").unwrap();
+    /// table.insert(vec![3_u64.into(), "Ali".into()]).unwrap();
+    /// table.insert(vec![1_u64.into(), "Cem".into()]).unwrap();
+    /// table.insert(vec![4_u64.into(), "Ali".into()]).unwrap();
+    ///
+    /// assert_eq!(table.min_where(|x| x.row("customer").is("Ali"), "quantity"), Ok(Types::U64(3)));
+    /// assert!(table.min_where(|_| true, "customer").is_err());
+    /// ```
+    pub fn min_where<E: Fn(Entries) -> bool>(
+        &self,
+        filter: E,
+        column: &str,
+    ) -> Result<Types, String> {
+        let (_, values) = self.numeric_where(filter, column)?;
+        values
+            .into_iter()
+            .min_by(|a, b| a.compare(b))
+            .ok_or_else(|| format!("No rows to compare in column '{}'", column))
+    }
+
+    /// Largest value of a numeric column over rows matching filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `column` - Name of the numeric column
+    /// ## Returns
+    /// * [`Ok<Types>`] Largest value with the type of the column
+    /// * [`Err<String>`] If column does not exist, is not numeric or no row matches
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs, Types};
+    /// let mut db = Database::new();
+    /// db.create_table("orders", vec![
+    ///    TableRow::new("price", TypeDefs::F32),
+    ///    TableRow::new("customer", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("orders").unwrap();
+    /// table.insert(vec![3.5_f32.into(), "Ali".into()]).unwrap();
+    /// table.insert(vec![9.0_f32.into(), "Cem".into()]).unwrap();
+    /// table.insert(vec![4.25_f32.into(), "Ali".into()]).unwrap();
+    ///
+    /// assert_eq!(table.max_where(|x| x.row("customer").is("Ali"), "price"), Ok(Types::F32(4.25)));
+    /// assert!(table.max_where(|x| x.row("customer").is("Bora"), "price").is_err());
+    /// ```
+    pub fn max_where<E: Fn(Entries) -> bool>(
+        &self,
+        filter: E,
+        column: &str,
+    ) -> Result<Types, String> {
+        let (_, values) = self.numeric_where(filter, column)?;
+        values
+            .into_iter()
+            .max_by(|a, b| a.compare(b))
+            .ok_or_else(|| format!("No rows to compare in column '{}'", column))
+    }
+
+    /// Type of a numeric column and its values in rows matching filter, shared by the `*_where` aggregates
+    fn numeric_where<E: Fn(Entries) -> bool>(
+        &self,
+        filter: E,
+        column: &str,
+    ) -> Result<(TypeDefs, Vec<Types>), String> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return Err(format!("Could not find key '{}' in table", column)),
+        };
+        let rtype = self.headers[position].rtype.clone();
+        match rtype {
+            TypeDefs::I8 | TypeDefs::I64 | TypeDefs::U64 | TypeDefs::F32 | TypeDefs::F64 => (),
+            ref rtype => return Err(format!("Column '{}' is not numeric, got {}", column, rtype)),
+        }
+        let values = (0..self.columns.len())
+            .filter(|index| self.row_at(*index).is_some_and(&filter))
+            .map(|index| self.columns[index][position].get_type())
+            .collect();
+        Ok((rtype, values))
+    }
+
+    /// Get the first row matching filter, stops scanning at the first match
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// [`Option<Entries>`] First matching row in table order
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("id", TypeDefs::I64),
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec![1_i64.into(), "Ahmet".into()]).unwrap();
+    /// table.insert(vec![2_i64.into(), "Mehmet".into()]).unwrap();
+    ///
+    /// let user = table.find_where(|entry| entry.row("id").is(2_i64)).unwrap();
+    /// assert_eq!(user.get("name").unwrap().get::<String>(), "Mehmet");
+    /// assert!(table.find_where(|entry| entry.row("id").is(3_i64)).is_none());
+    /// ```
+    pub fn find_where<E: Fn(Entries) -> bool>(&self, filter: E) -> Option<Entries> {
+        (0..self.columns.len())
+            .filter_map(|index| self.row_at(index))
+            .find(|entries| filter(entries.clone()))
+    }
+
+    /// Check whether any row matches filter, stops scanning at the first match
+    ///
+    /// For equality checks on a string column [`Table::has_string_value`] can skip the scan
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// [`bool`] `true` if a row matches
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("id", TypeDefs::I64),
+    ///    TableRow::new("name", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec![1_i64.into(), "Ahmet".into()]).unwrap();
+    ///
+    /// assert!(table.exists_where(|entry| entry.row("id").is(1_i64)));
+    /// assert!(!table.exists_where(|entry| entry.row("id").is(2_i64)));
+    /// ```
+    pub fn exists_where<E: Fn(Entries) -> bool>(&self, filter: E) -> bool {
+        self.find_where(filter).is_some()
+    }
+
+    /// Get matching rows together with their row index, so they can be addressed later without filtering again
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// [`Vec<(Entries, usize)>`] Matching rows and their index, in table order
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), 19_i64.into()]).unwrap();
+    /// table.insert(vec!["John".into(), 20_i64.into()]).unwrap();
+    /// table.insert(vec!["Ahmet".into(), 21_i64.into()]).unwrap();
+    ///
+    /// let found = table.get_where_and_indices(|entry| entry.row("name").is("Ahmet"));
+    /// let indices: Vec<usize> = found.iter().map(|(_, index)| *index).collect();
+    /// assert_eq!(indices, vec![0, 2]);
+    /// assert_eq!(table.get_at(2).unwrap().get("age").unwrap().get::<i64>(), 21);
+    /// ```
+    pub fn get_where_and_indices<E: Fn(Entries) -> bool + Clone + Sized>(
+        &self,
+        filter: E,
+    ) -> Vec<(Entries, usize)> {
+        let mut found_entries = Vec::new();
+        for index in 0..self.columns.len() {
+            if let Some(entries) = self.row_at(index) {
+                if filter(entries.clone()) {
+                    found_entries.push((entries, index));
+                }
+            }
+        }
+        found_entries
+    }
+
+    /// Get the value of a column by filter, stops after `limit` matching rows
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `limit` - Maximum number of rows to return
+    /// ## Returns
+    /// [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for age in 0..20_i64 {
+    ///     table.insert(vec!["Ahmet".into(), age.into()]).unwrap();
+    /// }
+    /// let found = table.get_where_limit(|entry| entry.row("name").is("Ahmet"), 10);
+    /// assert_eq!(found.len(), 10);
+    /// assert_eq!(found[9].get("age").unwrap().get::<i64>(), 9);
+    /// ```
+    pub fn get_where_limit<E: Fn(Entries) -> bool + Clone + Sized>(
+        &self,
+        filter: E,
+        limit: usize,
+    ) -> Vec<Entries> {
+        self.get_where_offset_limit(filter, 0, limit)
+    }
+
+    /// Get the value of a column by filter, skipping `offset` matching rows and stopping after `limit` rows
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `offset` - Number of matching rows to skip
+    /// * `limit` - Maximum number of rows to return
+    /// ## Returns
+    /// [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for age in 0..20_i64 {
+    ///     table.insert(vec!["Ahmet".into(), age.into()]).unwrap();
+    /// }
+    /// let page = table.get_where_offset_limit(|entry| entry.row("name").is("Ahmet"), 15, 10);
+    /// assert_eq!(page.len(), 5);
+    /// assert_eq!(page[0].get("age").unwrap().get::<i64>(), 15);
+    /// ```
+    pub fn get_where_offset_limit<E: Fn(Entries) -> bool + Clone + Sized>(
+        &self,
+        filter: E,
+        offset: usize,
+        limit: usize,
+    ) -> Vec<Entries> {
+        let mut found_entries = Vec::new();
+        if limit == 0 {
+            return found_entries;
+        }
+        let mut skipped = 0;
+        for fake_entries in (0..self.columns.len()).filter_map(|i| self.row_at(i)) {
+            if filter(fake_entries.clone()) {
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                found_entries.push(fake_entries);
+                if found_entries.len() == limit {
+                    break;
+                }
+            }
+        }
+        found_entries
+    }
+
+    /// Lazily iterate rows matching the filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// [`Iterator<Item = Entries>`] Rows are only built when the iterator advances
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for age in 0..20_i64 {
+    ///     table.insert(vec!["Ahmet".into(), age.into()]).unwrap();
+    /// }
+    /// let ages: Vec<i64> = table
+    ///     .iter_where(|entry| entry.row("name").is("Ahmet"))
+    ///     .skip(5)
+    ///     .take(3)
+    ///     .map(|entry| entry.get("age").unwrap().get())
+    ///     .collect();
+    /// assert_eq!(ages, vec![5, 6, 7]);
+    /// ```
+    pub fn iter_where<'a, E: Fn(Entries) -> bool + 'a>(
+        &'a self,
+        filter: E,
+    ) -> impl Iterator<Item = Entries> + 'a {
+        self.iter().filter(move |entries| filter(entries.clone()))
+    }
+
+    /// Get rows where any column equals the given value
+    /// ## Arguments
+    /// * `value` - Value to search for, array values are not supported
+    /// ## Returns
+    /// [`Vec<Entries>`] Empty if `value` is an array
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs, Types};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("city", TypeDefs::String),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), "Izmir".into()]).unwrap();
+    /// table.insert(vec!["Izmir".into(), "Ankara".into()]).unwrap();
+    /// table.insert(vec!["Seda".into(), "Ankara".into()]).unwrap();
+    /// let found = table.rows_where_any_column(&Types::String("Izmir".to_string()));
+    /// assert_eq!(found.len(), 2);
+    /// ```
+    pub fn rows_where_any_column(&self, value: &Types) -> Vec<Entries> {
+        if value.is_array() {
+            return vec![];
+        }
+        self.get_where(|x| x.entries.iter().any(|entry| &entry.value.rtype == value))
+    }
+
+    /// Get rows where every given column equals its expected value
+    /// ## Arguments
+    /// * `pattern` - Pairs of column name and expected value
+    /// ## Returns
+    /// [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs, Types};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), 22_i64.into()]).unwrap();
+    /// table.insert(vec!["Ahmet".into(), 30_i64.into()]).unwrap();
+    /// let found = table.rows_where_all_match(&[
+    ///     ("name".to_string(), Types::String("Ahmet".to_string())),
+    ///     ("age".to_string(), Types::I64(30)),
+    /// ]);
+    /// assert_eq!(found.len(), 1);
+    /// ```
+    pub fn rows_where_all_match(&self, pattern: &[(String, Types)]) -> Vec<Entries> {
+        self.get_where(|x| {
+            pattern.iter().all(
+                |(key, value)| matches!(x.get(key), Some(entry) if &entry.value.rtype == value),
+            )
+        })
+    }
+
+    /// Increase the value of a number by filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<Vec<String>>`] - Error messages
+    /// ## Example
+    /// ```rust
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().inc_where(|x| {
+    ///     x.row("age").exists()
+    /// }, "age");
+    /// //Increases all ages by 1
+    /// ```
+    pub fn inc_where<E: Fn(Entries) -> bool + Clone + Sized>(
+        &mut self,
+        filter: E,
+        row: &str,
+    ) -> Result<(), Vec<String>> {
+        let unique_values = self.take_unique_values();
+        self.invalidate_cache();
+        let mut previous = vec![];
+        let mut errors = vec![];
+        #[cfg(feature = "events")]
+        let mut updated = vec![];
+        for (index, entries) in self.columns.iter_mut().enumerate() {
+            let fake_entries = Entries {
+                entries: entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, value)| Entry {
+                        key: self.headers[ix].key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+            };
+
+            if filter(fake_entries.clone()) {
+                let header_pos = self.headers.iter().position(|x| x.key == row).unwrap();
+                match match entries[header_pos].clone().rtype {
+                    Types::I8(e) => {
+                        if e == std::i8::MAX {
+                            errors.push(format!("'I8' about to be overflow"));
+                            None
+                        } else {
+                            Some(Types::I8(e + 1))
+                        }
+                    }
+                    Types::I64(e) => {
+                        if e == i64::max_value() {
+                            errors.push(format!("'I64' about to be overflow"));
+                            None
+                        } else {
+                            Some(Types::I64(e + 1))
+                        }
+                    }
+                    Types::U64(e) => {
+                        if e == u64::max_value() {
+                            errors.push(format!("'U64' about to be overflow"));
+                            None
+                        } else {
+                            Some(Types::U64(e + 1))
+                        }
+                    }
+                    Types::F32(e) => {
+                        if e == f32::MAX {
+                            errors.push(format!("'F32' about to be overflow"));
+                            None
+                        } else {
+                            Some(Types::F32(e + 1.))
+                        }
+                    }
+                    Types::F64(e) => {
+                        if e == f64::MAX {
+                            errors.push(format!("'F64' about to be overflow"));
+                            None
+                        } else {
+                            Some(Types::F64(e + 1.))
+                        }
+                    }
+                    _ => {
+                        errors.push(format!("{} is not a integer type column", row));
+                        None
+                    }
+                } {
+                    Some(e) => {
+                        if unique_values.is_some() {
+                            previous.push((index, entries.clone()));
+                        }
+                        entries[header_pos].rtype = e;
+                        #[cfg(feature = "events")]
+                        updated.push(entries.clone());
+                    }
+                    None => {}
+                }
+            }
+        }
+        self.check_changed_rows(unique_values, previous)?;
+        #[cfg(feature = "events")]
+        for row in updated {
+            self.emit(EventType::Update, &row);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Decrease the value of a number by filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<Vec<String>>`] - Error messages
+    /// ## Example
+    /// ```rust
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("credits", TypeDefs::U64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 1_u64.into()]).unwrap();
+    /// table.dec_where(|x| {
+    ///     x.row("name").is("John")
+    /// }, "credits").unwrap();
+    /// //Decreases credits of John by 1
+    /// assert!(table.get_all()[0].row("credits").is(0_u64));
+    /// assert!(table.dec_where(|x| x.row("name").is("John"), "credits").is_err());
+    /// ```
+    pub fn dec_where<E: Fn(Entries) -> bool + Clone + Sized>(
+        &mut self,
+        filter: E,
+        row: &str,
+    ) -> Result<(), Vec<String>> {
+        let unique_values = self.take_unique_values();
+        self.invalidate_cache();
+        let mut previous = vec![];
+        let mut errors = vec![];
+        #[cfg(feature = "events")]
+        let mut updated = vec![];
+        for (index, entries) in self.columns.iter_mut().enumerate() {
+            let fake_entries = Entries {
+                entries: entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, value)| Entry {
+                        key: self.headers[ix].key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+            };
+
+            if filter(fake_entries.clone()) {
+                let header_pos = self.headers.iter().position(|x| x.key == row).unwrap();
+                if let Some(e) = match entries[header_pos].clone().rtype {
+                    Types::I8(e) => {
+                        if e == i8::MIN {
+                            errors.push("'I8' about to be underflow".to_string());
+                            None
+                        } else {
+                            Some(Types::I8(e - 1))
+                        }
+                    }
+                    Types::I64(e) => {
+                        if e == i64::MIN {
+                            errors.push("'I64' about to be underflow".to_string());
+                            None
+                        } else {
+                            Some(Types::I64(e - 1))
+                        }
+                    }
+                    Types::U64(e) => {
+                        if e == u64::MIN {
+                            errors.push("'U64' about to be underflow".to_string());
+                            None
+                        } else {
+                            Some(Types::U64(e - 1))
+                        }
+                    }
+                    Types::F32(e) => {
+                        if e == f32::MIN {
+                            errors.push("'F32' about to be underflow".to_string());
+                            None
+                        } else {
+                            Some(Types::F32(e - 1.))
+                        }
+                    }
+                    Types::F64(e) => {
+                        if e == f64::MIN {
+                            errors.push("'F64' about to be underflow".to_string());
+                            None
+                        } else {
+                            Some(Types::F64(e - 1.))
+                        }
+                    }
+                    _ => {
+                        errors.push(format!("{} is not a integer type column", row));
+                        None
+                    }
+                } {
+                    if unique_values.is_some() {
+                        previous.push((index, entries.clone()));
+                    }
+                    entries[header_pos].rtype = e;
+                    #[cfg(feature = "events")]
+                    updated.push(entries.clone());
+                }
+            }
+        }
+        self.check_changed_rows(unique_values, previous)?;
+        #[cfg(feature = "events")]
+        for row in updated {
+            self.emit(EventType::Update, &row);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Increase the value of a number by filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<Vec<String>>`] - Error messages
+    /// ## Example
+    /// ```rust
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().inc_where(|x| {
+    ///     x.row("age").exists()
+    /// }, "age");
+    /// //Increases all ages by 1
+    /// ```
+    pub fn push_where<E: Fn(Entries) -> bool + Clone + Sized>(
+        &mut self,
+        filter: E,
+        row: &str,
+        value: SafeType,
+    ) -> Result<(), Vec<String>> {
+        let unique_values = self.take_unique_values();
+        self.invalidate_cache();
+        let mut previous = vec![];
+        let mut errors = vec![];
+        #[cfg(feature = "events")]
+        let mut updated = vec![];
+        for (index, entries) in self.columns.iter_mut().enumerate() {
+            let fake_entries = Entries {
+                entries: entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, value)| Entry {
+                        key: self.headers[ix].key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+            };
+
+            if filter(fake_entries.clone()) {
+                let header_pos = self.headers.iter().position(|x| x.key == row).unwrap();
+                match match entries[header_pos].clone().rtype {
+                    Types::Array(e) => {
+                        let type_inner_type = entries[header_pos]
+                            .clone()
+                            .get_type_def()
+                            .inner_type()
+                            .unwrap();
+
+                        if value.get_type().is_array() {
+                            let value_inner_type = value.get_type_def().inner_type().unwrap();
+
+                            if type_inner_type == value_inner_type {
+                                let mut arr_copy = e.clone();
+                                let contents = match value.get_type() {
+                                    Types::Array(c) => c,
+                                    _ => unreachable!(),
+                                };
+                                arr_copy.extend(contents);
+                                Some(Types::Array(arr_copy))
+                            } else {
+                                errors.push(format!(
+                                    "Inner type of array is '{}' but given value is '{}'",
+                                    type_inner_type,
+                                    value.get_type()
+                                ));
+                                None
+                            }
+                        } else {
+                            let mut arr_copy = e.clone();
+
+                            if type_inner_type == value.get_type_def() {
+                                arr_copy.push(value.clone());
+                                Some(Types::Array(arr_copy))
+                            } else {
+                                errors.push(format!(
+                                    "Inner type of array is '{}' but given value is '{}'",
+                                    type_inner_type,
+                                    value.get_type()
+                                ));
+                                None
+                            }
+                        }
+                    }
+                    _ => {
+                        errors.push("Field is not a array type".to_string());
+                        None
+                    }
+                } {
+                    Some(e) => {
+                        if unique_values.is_some() {
+                            previous.push((index, entries.clone()));
+                        }
+                        entries[header_pos].rtype = e;
+                        #[cfg(feature = "events")]
+                        updated.push(entries.clone());
+                    }
+                    None => (),
+                }
+            }
+        }
+        self.check_changed_rows(unique_values, previous)?;
+        #[cfg(feature = "events")]
+        for row in updated {
+            self.emit(EventType::Update, &row);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Set the value of a single column by filter, shorthand for [`Table::set_where`] with one [`Entry`]
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `column` - Name of the column to set
+    /// * `value` - Value to set
+    /// ## Returns
+    /// * [`Ok<usize>`] - Effected row length
+    /// * [`Err<Vec<String>>`] - Error messages
+    /// ## Example
+    /// ```rust
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), 18_i64.into()]).unwrap();
+    /// table.set_column_value_where(|x| x.row("name").is("Ahmet".to_string()), "age", 19_i64.into()).unwrap();
+    /// assert_eq!(table.get_at(0).unwrap().get("age").unwrap().get::<i64>(), 19);
+    /// ```
+    pub fn set_column_value_where<E: Fn(Entries) -> bool + Clone + Sized>(
+        &mut self,
+        filter: E,
+        column: &str,
+        value: SafeType,
+    ) -> Result<usize, Vec<String>> {
+        self.set_where::<E, Types>(
+            filter,
+            vec![Entry {
+                key: column.to_string(),
+                value,
+            }],
+        )
+    }
+
+    /// Overwrite every value of a column, `values[i]` is written to row `i`
+    ///
+    /// Values are checked before the table is touched
+    /// ## Arguments
+    /// * `column` - Name of the column to set
+    /// * `values` - One value per row, in row order
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<BulkSetError>`] If column does not exist, value count is not the row count, a value has another type
+    ///   or a unique column would hold a value twice
+    /// ## Example
+    /// ```rust
+    /// use safe_en::Database;
+    /// use safe_en::table::{BulkSetError, TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Ahmet".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["Mehmet".into(), 21_i64.into()]).unwrap();
+    ///
+    /// table.bulk_set_column("age", vec![19_i64.into(), 22_i64.into()]).unwrap();
+    /// assert_eq!(table.get_at(1).unwrap().get("age").unwrap().get::<i64>(), 22);
+    /// assert_eq!(
+    ///     table.bulk_set_column("age", vec![19_i64.into()]),
+    ///     Err(BulkSetError::LengthMismatch { expected: 2, got: 1 })
+    /// );
+    /// assert_eq!(
+    ///     table.bulk_set_column("age", vec![19_i64.into(), "22".into()]),
+    ///     Err(BulkSetError::TypeMismatch { row: 1, expected: TypeDefs::I64, got: TypeDefs::String })
+    /// );
+    /// ```
+    pub fn bulk_set_column(
+        &mut self,
+        column: &str,
+        values: Vec<SafeType>,
+    ) -> Result<(), BulkSetError> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return Err(BulkSetError::ColumnNotFound(column.to_string())),
+        };
+        if values.len() != self.columns.len() {
+            return Err(BulkSetError::LengthMismatch {
+                expected: self.columns.len(),
+                got: values.len(),
+            });
+        }
+        let rtype = &self.headers[position].rtype;
+        if let Some((row, value)) = values
+            .iter()
+            .enumerate()
+            .find(|(_, value)| &value.get_type_def() != rtype)
+        {
+            return Err(BulkSetError::TypeMismatch {
+                row,
+                expected: rtype.clone(),
+                got: value.get_type_def(),
+            });
+        }
+
+        let mut unique_values = self.take_unique_values();
+        if let Some(column_values) = unique_values.as_mut().and_then(|x| x.get_mut(&position)) {
+            column_values.clear();
+            for (row, value) in values.iter().enumerate() {
+                if !column_values.insert(utils::value_bytes(value)) {
+                    return Err(BulkSetError::UniqueViolation { row });
+                }
+            }
+        }
+        self.invalidate_cache();
+        for (index, value) in values.into_iter().enumerate() {
+            self.columns[index][position] = value;
+        }
+        self.keep_unique_values(unique_values);
+        #[cfg(feature = "events")]
+        for row in self.columns.clone() {
+            self.emit(EventType::Update, &row);
+        }
+        Ok(())
+    }
+
+    /// Set the value of a column by filter
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `value` - Value to set
+    /// ## Returns
+    /// * [`Ok<usize>`] - Effected row length
+    /// * [`Err<Vec<String>>`] - Error messages
+    /// ## Example
+    /// ```rust
+    /// use safe_en::Database;
+    /// use safe_en::table::{TableRow, TypeDefs};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// db.table("users").unwrap().set_where(|x| {
+    ///     x.row("name").is("Ahmet".to_string())
+    /// }, vec![
+    ///     safe_en::table::Entry {
+    ///         key: "name".to_string(),
+    ///         value: "Ahmetcan".into(),
+    ///     },
+    /// ]);
+    /// ```
+    pub fn set_where<E: Fn(Entries) -> bool + Clone + Sized, T>(
+        &mut self,
+        filter: E,
+        value: Vec<Entry>,
+    ) -> Result<usize, Vec<String>>
+    where
+        Types: From<T>,
+        T: Clone,
+    {
+        let unique_values = self.take_unique_values();
+        self.invalidate_cache();
+        let mut previous = vec![];
+        let mut changed_rows = 0;
+        let mut errors = vec![];
+        if value.len() > self.headers.len() {
+            errors.push("Value length is not equal to header length".to_string());
+            return Err(errors);
+        }
+        #[cfg(feature = "events")]
+        let mut updated = vec![];
+        'entryloop: for (index, entries) in self.columns.iter_mut().enumerate() {
             let fake_entries = Entries {
                 entries: entries
                     .iter()
@@ -1506,315 +6160,1630 @@ impl Table {
                     .collect(),
             };
 
-            if filter(fake_entries.clone()) {
-                let header_pos = self.headers.iter().position(|x| x.key == row).unwrap();
-                match match entries[header_pos].clone().rtype {
-                    Types::I8(e) => {
-                        if e == std::i8::MAX {
-                            errors.push(format!("'I8' about to be overflow"));
-                            None
-                        } else {
-                            Some(Types::I8(e + 1))
-                        }
-                    }
-                    Types::I64(e) => {
-                        if e == i64::max_value() {
-                            errors.push(format!("'I64' about to be overflow"));
-                            None
-                        } else {
-                            Some(Types::I64(e + 1))
-                        }
-                    }
-                    Types::U64(e) => {
-                        if e == u64::max_value() {
-                            errors.push(format!("'U64' about to be overflow"));
-                            None
-                        } else {
-                            Some(Types::U64(e + 1))
+            if filter(fake_entries.clone()) {
+                if unique_values.is_some() {
+                    previous.push((index, entries.clone()));
+                }
+                for value_entry in value.iter() {
+                    let targt = fake_entries
+                        .entries
+                        .iter()
+                        .find(|x| x.key == value_entry.key);
+                    if let Some(target) = targt {
+                        if target.value.get_type_def() == value_entry.value.get_type_def() {
+                            let header_pos = self
+                                .headers
+                                .iter()
+                                .position(|x| x.key == value_entry.key)
+                                .unwrap();
+                            changed_rows += 1;
+                            entries[header_pos] = value_entry.value.clone();
+                        } else {
+                            errors.push(format!(
+                                "Value type is not equal to header type. Header: {}, Value: {}",
+                                target.value.get_type_def(),
+                                value_entry.value.get_type_def()
+                            ));
+                            break 'entryloop;
+                        }
+                    } else {
+                        errors.push(format!("Could not find key '{}' in table", value_entry.key));
+                        break 'entryloop;
+                    }
+                }
+                #[cfg(feature = "events")]
+                updated.push(entries.clone());
+            }
+        }
+        self.check_changed_rows(unique_values, previous)?;
+        #[cfg(feature = "events")]
+        for row in updated {
+            self.emit(EventType::Update, &row);
+        }
+        if errors.is_empty() {
+            Ok(changed_rows)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Insert data to table
+    ///
+    /// Trailing columns left out of `rows` are filled from defaults set by [`crate::TableBuilder::default`]
+    /// ## Arguments
+    /// * `rows` - [`TableRow`]
+    /// ## Returns
+    /// * [`Result<()>`]
+    /// * [`Err<Vec<String>>`] for insert errors, including unique constraint violations
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::{TableRow, TypeDefs, Types},Database};
+    /// let mut db = Database::new();
+    /// db.create_table(
+    ///    "test",
+    ///   vec![
+    ///      TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]).unwrap();
+    ///
+    /// db.table("test").unwrap()
+    ///    .insert(vec![
+    ///       "John".into(),
+    ///      18_i64.into(),
+    ///     ]).unwrap();
+    /// ```
+    pub fn insert(&mut self, rows: Vec<SafeType>) -> Result<(), Vec<String>> {
+        let rows = self.validate_row(rows)?;
+        let mut unique_values = self.take_unique_values();
+        if let Some(sets) = &mut unique_values {
+            Table::add_unique_values(sets, [(self.columns.len(), rows.as_slice())])
+                .map_err(|violation| self.unique_error(violation))?;
+        }
+        self.invalidate_cache();
+        self.columns.push(rows.clone());
+        self.keep_unique_values(unique_values);
+        #[cfg(feature = "events")]
+        self.emit(EventType::Insert, &rows);
+        Ok(())
+    }
+
+    /// Insert data to table keeping rows sorted by `sort_column` in ascending order
+    ///
+    /// Insertion point is found with a binary search, so rows must already be sorted.
+    /// A row equal to existing rows is placed after them
+    /// ## Arguments
+    /// * `rows` - [`TableRow`]
+    /// * `sort_column` - Name of the column rows are sorted by
+    /// ## Returns
+    /// * [`Ok<usize>`] Index of the inserted row
+    /// * [`Err<Vec<String>>`] for insert errors, same as [`Table::insert`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::{TableRow, TypeDefs}, Database};
+    /// let mut db = Database::new();
+    /// db.create_table(
+    ///     "leaderboard",
+    ///     vec![
+    ///         TableRow::new("name", TypeDefs::String),
+    ///         TableRow::new("score", TypeDefs::I64),
+    ///     ]).unwrap();
+    ///
+    /// let table = db.table("leaderboard").unwrap();
+    /// assert_eq!(table.insert_sorted(vec!["Ali".into(), 30_i64.into()], "score"), Ok(0));
+    /// assert_eq!(table.insert_sorted(vec!["Cem".into(), 10_i64.into()], "score"), Ok(0));
+    /// assert_eq!(table.insert_sorted(vec!["Bora".into(), 20_i64.into()], "score"), Ok(1));
+    ///
+    /// let names: Vec<String> = table.get_all().iter().map(|x| x.get("name").unwrap().get()).collect();
+    /// assert_eq!(names, vec!["Cem", "Bora", "Ali"]);
+    /// ```
+    pub fn insert_sorted(
+        &mut self,
+        rows: Vec<SafeType>,
+        sort_column: &str,
+    ) -> Result<usize, Vec<String>> {
+        let position = match self.headers.iter().position(|x| x.key == sort_column) {
+            Some(e) => e,
+            None => {
+                return Err(vec![format!(
+                    "Could not find key '{}' in table",
+                    sort_column
+                )])
+            }
+        };
+        self.insert(rows)?;
+
+        let row = self.columns.pop().unwrap();
+        let index = self.columns.partition_point(|x| {
+            x[position].rtype.compare(&row[position].rtype) != Ordering::Greater
+        });
+        self.columns.insert(index, row);
+        Ok(index)
+    }
+
+    /// Insert data to table, replacing every row that has the same value in `key_column`
+    ///
+    /// Row is checked like [`Table::insert`], trailing columns are filled from defaults
+    /// and other unique columns must not repeat a value of the rows left in the table
+    /// ## Arguments
+    /// * `key_column` - Name of the column used to find existing rows
+    /// * `rows` - [`TableRow`]
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<Vec<String>>`] for insert errors, table is left untouched
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::{TableRow, TypeDefs},Database};
+    /// let mut db = Database::new();
+    /// db.create_table(
+    ///     "cache",
+    ///     vec![
+    ///         TableRow::new("key", TypeDefs::String),
+    ///         TableRow::new("value", TypeDefs::I64),
+    ///     ]).unwrap();
+    ///
+    /// let table = db.table("cache").unwrap();
+    /// table.insert_or_replace("key", vec!["hits".into(), 1_i64.into()]).unwrap();
+    /// table.insert_or_replace("key", vec!["hits".into(), 2_i64.into()]).unwrap();
+    ///
+    /// let entries = table.get_all();
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(entries[0].get("value").unwrap().get::<i64>(), 2);
+    /// ```
+    pub fn insert_or_replace(
+        &mut self,
+        key_column: &str,
+        rows: Vec<SafeType>,
+    ) -> Result<(), Vec<String>> {
+        let header_pos = match self.headers.iter().position(|x| x.key == key_column) {
+            Some(e) => e,
+            None => {
+                return Err(vec![format!(
+                    "Could not find key '{}' in table",
+                    key_column
+                )])
+            }
+        };
+        let rows = self.validate_row(rows)?;
+
+        let key = rows[header_pos].clone();
+        let mut unique_values = self.take_unique_values();
+        if let Some(sets) = &mut unique_values {
+            let replaced = self
+                .columns
+                .iter()
+                .filter(|column| column[header_pos] == key)
+                .map(|column| column.as_slice());
+            Table::remove_unique_values(sets, replaced);
+            let index = self
+                .columns
+                .iter()
+                .filter(|column| column[header_pos] != key)
+                .count();
+            Table::add_unique_values(sets, [(index, rows.as_slice())])
+                .map_err(|violation| self.unique_error(violation))?;
+        }
+        self.invalidate_cache();
+        #[cfg(feature = "events")]
+        let removed: Vec<Vec<SafeType>> = self
+            .columns
+            .iter()
+            .filter(|column| column[header_pos] == key)
+            .cloned()
+            .collect();
+        self.columns.retain(|column| column[header_pos] != key);
+        self.columns.push(rows);
+        self.keep_unique_values(unique_values);
+        #[cfg(feature = "events")]
+        {
+            for row in removed {
+                self.emit(EventType::Delete, &row);
+            }
+            let inserted = self.columns.last().unwrap().clone();
+            self.emit(EventType::Insert, &inserted);
+        }
+        Ok(())
+    }
+
+    /// Insert many rows at once
+    ///
+    /// Every row is checked before any is inserted, trailing columns are filled from defaults like [`Table::insert`]
+    /// ## Arguments
+    /// * `rows` - Rows to insert
+    /// ## Returns
+    /// * [`Ok<usize>`] Number of inserted rows
+    /// * [`Err<Vec<String>>`] Errors of all invalid rows, nothing is inserted
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::{TableRow, TypeDefs}, Database};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ]).unwrap();
+    /// let table = db.table("users").unwrap();
+    /// let rows = (0..100_i64).map(|x| vec![format!("user{}", x).into(), x.into()]).collect();
+    /// assert_eq!(table.insert_many(rows), Ok(100));
+    ///
+    /// let errors = table.insert_many(vec![
+    ///     vec!["John".into(), 18_i64.into()],
+    ///     vec!["Jane".into(), "21".into()],
+    ///     vec!["Jack".into()],
+    /// ]).unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(table.get_all().len(), 100);
+    /// ```
+    pub fn insert_many(&mut self, rows: Vec<Vec<SafeType>>) -> Result<usize, Vec<String>> {
+        let mut errors = vec![];
+        let mut valid_rows = Vec::with_capacity(rows.len());
+        for (row_index, row) in rows.into_iter().enumerate() {
+            match self.validate_row(row) {
+                Ok(row) => valid_rows.push(row),
+                Err(row_errors) => errors.extend(
+                    row_errors
+                        .into_iter()
+                        .map(|error| format!("{} on row {}", error, row_index)),
+                ),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let row_count = self.columns.len();
+        let mut unique_values = self.take_unique_values();
+        if let Some(sets) = &mut unique_values {
+            let rows = valid_rows
+                .iter()
+                .enumerate()
+                .map(|(offset, row)| (row_count + offset, row.as_slice()));
+            Table::add_unique_values(sets, rows)
+                .map_err(|violation| self.unique_error(violation))?;
+        }
+        self.invalidate_cache();
+        let count = valid_rows.len();
+        self.columns.extend(valid_rows);
+        self.keep_unique_values(unique_values);
+        #[cfg(feature = "events")]
+        let inserted = self.columns[row_count..].to_vec();
+        #[cfg(feature = "events")]
+        for row in inserted {
+            self.emit(EventType::Insert, &row);
+        }
+        Ok(count)
+    }
+
+    /// Insert rows returned by another table with the same schema
+    ///
+    /// Entries are matched to columns by key and inserted with [`Table::insert_many`],
+    /// import is all or nothing, if any row fails no row is inserted
+    /// ## Arguments
+    /// * `entries` - Rows to insert
+    /// ## Returns
+    /// * [`Ok<usize>`] Number of inserted rows
+    /// * [`Err<Vec<String>>`] for schema or insert errors, table is left untouched
+    /// ## Example
+    /// ```
+    /// use safe_en::{table::{TableRow, TypeDefs}, Database};
+    /// let mut db = Database::new();
+    /// let rows = vec![
+    ///     TableRow::new("name", TypeDefs::String),
+    ///     TableRow::new("age", TypeDefs::I64),
+    /// ];
+    /// db.create_table("users", rows.clone()).unwrap();
+    /// db.create_table("backup", rows).unwrap();
+    /// db.table("users").unwrap().insert(vec!["John".into(), 18_i64.into()]).unwrap();
+    /// db.table("users").unwrap().insert(vec!["Jane".into(), 21_i64.into()]).unwrap();
+    ///
+    /// let entries = db.table("users").unwrap().get_all();
+    /// assert_eq!(db.table("backup").unwrap().import_entries(entries.clone()), Ok(2));
+    /// assert_eq!(db.table("backup").unwrap().get_all().len(), 2);
+    ///
+    /// db.build_table("members")
+    ///     .column("name", TypeDefs::String)
+    ///     .column("age", TypeDefs::I64)
+    ///     .unique("name")
+    ///     .finish()
+    ///     .unwrap();
+    /// let members = db.table("members").unwrap();
+    /// members.insert(vec!["Jane".into(), 21_i64.into()]).unwrap();
+    /// // Jane is already a member, John is not imported either
+    /// assert!(members.import_entries(entries).is_err());
+    /// assert_eq!(members.get_all().len(), 1);
+    /// ```
+    pub fn import_entries(&mut self, entries: Vec<Entries>) -> Result<usize, Vec<String>> {
+        let mut errors = vec![];
+        let mut rows = vec![];
+        for (row_index, entry) in entries.iter().enumerate() {
+            if entry.entries.len() != self.headers.len() {
+                errors.push(format!(
+                    "Length mismatch, expected {}, got {} length of column on row {}",
+                    self.headers.len(),
+                    entry.entries.len(),
+                    row_index
+                ));
+                continue;
+            }
+            let mut row = vec![];
+            for header in &self.headers {
+                match entry.entries.iter().find(|x| x.key == header.key) {
+                    Some(e) if e.value.get_type_def() == header.rtype => row.push(e.value.clone()),
+                    Some(e) => errors.push(format!(
+                        "Type mismatch, expected {}, got {} on column {} on row {}",
+                        header.rtype,
+                        e.value.get_type_def(),
+                        header.key,
+                        row_index
+                    )),
+                    None => errors.push(format!(
+                        "Could not find key '{}' on row {}",
+                        header.key, row_index
+                    )),
+                }
+            }
+            rows.push(row);
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.insert_many(rows)
+    }
+
+    /// Values of a numeric column converted to f64, in row order
+    fn numeric_values(&self, column: &str) -> Result<Vec<f64>, String> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return Err(format!("Could not find key '{}' in table", column)),
+        };
+        match self.headers[position].rtype {
+            TypeDefs::I8 | TypeDefs::I64 | TypeDefs::U64 | TypeDefs::F32 | TypeDefs::F64 => (),
+            ref rtype => return Err(format!("Column '{}' is not numeric, got {}", column, rtype)),
+        }
+        Ok(self
+            .columns
+            .iter()
+            .map(|row| match row[position].get_type() {
+                Types::I8(e) => e as f64,
+                Types::I64(e) => e as f64,
+                Types::U64(e) => e as f64,
+                Types::F32(e) => e as f64,
+                Types::F64(e) => e,
+                _ => unreachable!(),
+            })
+            .collect())
+    }
+
+    /// Aggregate a numeric column over a moving window of rows
+    ///
+    /// Window of row `i` covers rows `i..i + window_size`, shrinking at the end of the table.
+    /// Unknown or non numeric columns and a `window_size` of 0 return no rows
+    /// ## Arguments
+    /// * `column` - Name of the numeric column
+    /// * `window_size` - Number of rows in each window
+    /// * `agg` - [`AggFunc`] applied to each window
+    /// ## Returns
+    /// [`Vec<(usize, f64)>`] Row index and aggregate of its window
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{AggFunc, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("prices", vec![
+    ///    TableRow::new("price", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("prices").unwrap();
+    /// for price in [1_i64, 2, 3, 4] {
+    ///     table.insert(vec![price.into()]).unwrap();
+    /// }
+    /// assert_eq!(
+    ///     table.window_aggregate("price", 2, AggFunc::Sum),
+    ///     vec![(0, 3.), (1, 5.), (2, 7.), (3, 4.)]
+    /// );
+    /// assert_eq!(table.window_aggregate("price", 3, AggFunc::Max)[0], (0, 3.));
+    /// assert_eq!(table.window_aggregate("price", 3, AggFunc::Count)[3], (3, 1.));
+    /// ```
+    pub fn window_aggregate(
+        &self,
+        column: &str,
+        window_size: usize,
+        agg: AggFunc,
+    ) -> Vec<(usize, f64)> {
+        let values = match self.numeric_values(column) {
+            Ok(e) => e,
+            Err(_) => return vec![],
+        };
+        if window_size == 0 {
+            return vec![];
+        }
+        (0..values.len())
+            .map(|i| {
+                let window = &values[i..(i + window_size).min(values.len())];
+                let sum: f64 = window.iter().sum();
+                let value = match agg {
+                    AggFunc::Sum => sum,
+                    AggFunc::Mean => sum / window.len() as f64,
+                    AggFunc::Min => window.iter().cloned().fold(f64::INFINITY, f64::min),
+                    AggFunc::Max => window.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                    AggFunc::Count => window.len() as f64,
+                };
+                (i, value)
+            })
+            .collect()
+    }
+
+    /// Profile data quality of every column in a single pass over the rows
+    /// ## Returns
+    /// [`DataProfile`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{SafeType, TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("email", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::option_of(TypeDefs::I64)),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for (email, age) in [("a@mail.com", Some(18_i64)), ("", None), ("a@mail.com", None), ("b@mail.com", Some(21))] {
+    ///     let age = match age {
+    ///         Some(age) => SafeType::some(age.into()),
+    ///         None => SafeType::none(TypeDefs::I64),
+    ///     };
+    ///     table.insert(vec![email.into(), age]).unwrap();
+    /// }
+    ///
+    /// let profile = table.profile();
+    /// assert_eq!(profile.total_rows, 4);
+    /// let email = &profile.per_column[0];
+    /// assert_eq!(email.null_rate, 0.25);
+    /// assert_eq!(email.completeness, 0.75);
+    /// assert_eq!(email.unique_rate, 0.75);
+    /// assert_eq!(email.most_common_value, Some(Types::String("a@mail.com".to_string())));
+    /// assert_eq!(email.least_common_value, Some(Types::String("".to_string())));
+    /// let age = &profile.per_column[1];
+    /// assert_eq!(age.null_rate, 0.5);
+    /// assert_eq!(age.completeness, 0.5);
+    /// ```
+    pub fn profile(&self) -> DataProfile {
+        let mut counts: Vec<HashMap<Vec<u8>, (usize, usize)>> =
+            vec![HashMap::new(); self.headers.len()];
+        let mut empty = vec![0; self.headers.len()];
+        for (index, row) in self.columns.iter().enumerate() {
+            for (position, value) in row.iter().enumerate() {
+                counts[position]
+                    .entry(utils::value_bytes(value))
+                    .or_insert((0, index))
+                    .0 += 1;
+                match &value.rtype {
+                    Types::String(e) if e.is_empty() => empty[position] += 1,
+                    Types::Array(e) if e.is_empty() => empty[position] += 1,
+                    Types::Option(None) => empty[position] += 1,
+                    _ => (),
+                }
+            }
+        }
+
+        let total = self.columns.len();
+        let rate = |count: usize| {
+            if total == 0 {
+                0.
+            } else {
+                count as f64 / total as f64
+            }
+        };
+        let per_column = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(position, header)| {
+                let value_at =
+                    |(_, index): &(usize, usize)| self.columns[*index][position].get_type();
+                let column_counts = &counts[position];
+                ColumnProfile {
+                    name: header.key.clone(),
+                    null_rate: rate(empty[position]),
+                    unique_rate: rate(column_counts.len()),
+                    most_common_value: column_counts
+                        .values()
+                        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+                        .map(value_at),
+                    least_common_value: column_counts
+                        .values()
+                        .min_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)))
+                        .map(value_at),
+                    completeness: if total == 0 {
+                        0.
+                    } else {
+                        1. - rate(empty[position])
+                    },
+                }
+            })
+            .collect();
+        DataProfile {
+            total_rows: total,
+            per_column,
+        }
+    }
+
+    /// Infer column types of raw text rows, for imports without a schema
+    ///
+    /// A column is [`TypeDefs::I64`] if every value parses as i64, otherwise [`TypeDefs::F64`] if every value
+    /// parses as f64, otherwise [`TypeDefs::Bool`] if every value is `true` or `false`, otherwise [`TypeDefs::String`].
+    /// Columns without values are strings, short rows do not count for their missing columns
+    /// ## Arguments
+    /// * `raw_rows` - Rows of text values
+    /// ## Returns
+    /// [`Vec<TypeDefs>`] Type of each column
+    /// ## Example
+    /// ```
+    /// use safe_en::table::{Table, TypeDefs};
+    /// let rows = vec![
+    ///     vec!["1".to_string(), "1.5".to_string(), "true".to_string(), "John".to_string()],
+    ///     vec!["2".to_string(), "3".to_string(), "false".to_string(), "12".to_string()],
+    /// ];
+    /// assert_eq!(
+    ///     Table::infer_types(rows),
+    ///     vec![TypeDefs::I64, TypeDefs::F64, TypeDefs::Bool, TypeDefs::String]
+    /// );
+    /// ```
+    pub fn infer_types(raw_rows: Vec<Vec<String>>) -> Vec<TypeDefs> {
+        let column_count = raw_rows.iter().map(|x| x.len()).max().unwrap_or(0);
+        (0..column_count)
+            .map(|index| {
+                let values: Vec<&String> = raw_rows.iter().filter_map(|x| x.get(index)).collect();
+                if values.is_empty() {
+                    TypeDefs::String
+                } else if values.iter().all(|x| x.parse::<i64>().is_ok()) {
+                    TypeDefs::I64
+                } else if values.iter().all(|x| x.parse::<f64>().is_ok()) {
+                    TypeDefs::F64
+                } else if values.iter().all(|x| *x == "true" || *x == "false") {
+                    TypeDefs::Bool
+                } else {
+                    TypeDefs::String
+                }
+            })
+            .collect()
+    }
+
+    /// Summarize every column of the table
+    ///
+    /// Numeric columns get min, max and mean, string, char and bool columns get their most frequent value
+    /// ## Returns
+    /// [`TableSummary`] which can be printed as a table
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 18_i64.into()]).unwrap();
+    /// table.insert(vec!["Jane".into(), 30_i64.into()]).unwrap();
+    /// table.insert(vec!["John".into(), 24_i64.into()]).unwrap();
+    ///
+    /// let summary = table.summarize();
+    /// assert_eq!(summary.row_count, 3);
+    /// assert_eq!(summary.columns[0].distinct_count, Some(2));
+    /// assert_eq!(summary.columns[0].mode, Some(Types::String("John".to_string())));
+    /// assert_eq!(summary.columns[1].mean, Some(24.));
+    /// println!("{}", summary);
+    /// ```
+    pub fn summarize(&self) -> TableSummary {
+        let columns = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(position, header)| {
+                let mut counts: HashMap<Vec<u8>, (usize, usize)> = HashMap::new();
+                for (index, row) in self.columns.iter().enumerate() {
+                    counts
+                        .entry(utils::value_bytes(&row[position]))
+                        .or_insert((0, index))
+                        .0 += 1;
+                }
+                let values = self.numeric_values(&header.key).ok();
+                let mode = match header.rtype {
+                    TypeDefs::String | TypeDefs::Char | TypeDefs::Bool => counts
+                        .values()
+                        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+                        .map(|(_, index)| self.columns[*index][position].get_type()),
+                    _ => None,
+                };
+                let numeric = values.filter(|x| !x.is_empty());
+                ColumnSummary {
+                    name: header.key.clone(),
+                    rtype: header.rtype.clone(),
+                    distinct_count: Some(counts.len()).filter(|x| *x <= LOW_CARDINALITY_LIMIT),
+                    min: numeric
+                        .as_ref()
+                        .map(|x| x.iter().cloned().fold(f64::INFINITY, f64::min)),
+                    max: numeric
+                        .as_ref()
+                        .map(|x| x.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
+                    mean: numeric
+                        .as_ref()
+                        .map(|x| x.iter().sum::<f64>() / x.len() as f64),
+                    mode,
+                }
+            })
+            .collect();
+        TableSummary {
+            name: self.name.clone(),
+            column_count: self.headers.len(),
+            row_count: self.columns.len(),
+            columns,
+        }
+    }
+
+    /// Fill missing cells of an option column
+    /// ## Arguments
+    /// * `column` - Name of the option column
+    /// * `strategy` - [`FillStrategy`] picking the filled values
+    /// ## Returns
+    /// * [`Ok<usize>`] Number of filled cells
+    /// * [`Err<String>`] If column does not exist or is not an option, a constant has the wrong type,
+    ///   mean and median are used on a non numeric column or filled cells break a unique constraint
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{FillStrategy, SafeType, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("readings", vec![
+    ///    TableRow::new("value", TypeDefs::option_of(TypeDefs::F64)),
+    /// ]);
+    /// let table = db.table("readings").unwrap();
+    /// table.insert(vec![SafeType::none(TypeDefs::F64)]).unwrap();
+    /// table.insert(vec![SafeType::some(1.0_f64.into())]).unwrap();
+    /// table.insert(vec![SafeType::none(TypeDefs::F64)]).unwrap();
+    /// table.insert(vec![SafeType::some(3.0_f64.into())]).unwrap();
+    ///
+    /// let mut forward = table.clone();
+    /// assert_eq!(forward.fill_missing("value", FillStrategy::Forward), Ok(1));
+    /// assert_eq!(forward.get_all()[2].get("value").unwrap().value, SafeType::some(1.0_f64.into()));
+    ///
+    /// assert_eq!(table.fill_missing("value", FillStrategy::Mean), Ok(2));
+    /// assert_eq!(table.get_all()[0].get("value").unwrap().value, SafeType::some(2.0_f64.into()));
+    /// assert!(table.fill_missing("value", FillStrategy::Constant(1_i64.into())).is_err());
+    /// ```
+    pub fn fill_missing(&mut self, column: &str, strategy: FillStrategy) -> Result<usize, String> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return Err(format!("Could not find key '{}' in table", column)),
+        };
+        let inner = match &self.headers[position].rtype {
+            TypeDefs::Option(e) => *e.clone(),
+            rtype => {
+                return Err(format!(
+                    "Column '{}' is not an option, got {}",
+                    column, rtype
+                ))
+            }
+        };
+        let present: Vec<Option<SafeType>> = self
+            .columns
+            .iter()
+            .map(|row| match &row[position].rtype {
+                Types::Option(e) => e.as_ref().map(|x| *x.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let numeric = |values: Vec<f64>, reduce: fn(&mut Vec<f64>) -> f64| {
+            let mut values = values;
+            let value = if values.is_empty() {
+                0.
+            } else {
+                reduce(&mut values)
+            };
+            let rtype = match inner {
+                TypeDefs::I8 => Types::I8(value.round() as i8),
+                TypeDefs::I64 => Types::I64(value.round() as i64),
+                TypeDefs::U64 => Types::U64(value.round() as u64),
+                TypeDefs::F32 => Types::F32(value as f32),
+                TypeDefs::F64 => Types::F64(value),
+                _ => return Err(format!("Column '{}' is not numeric, got {}", column, inner)),
+            };
+            Ok(SafeType::build(rtype, inner.clone()))
+        };
+        let present_numbers = || -> Vec<f64> {
+            present
+                .iter()
+                .flatten()
+                .filter_map(|x| match x.rtype {
+                    Types::I8(e) => Some(e as f64),
+                    Types::I64(e) => Some(e as f64),
+                    Types::U64(e) => Some(e as f64),
+                    Types::F32(e) => Some(e as f64),
+                    Types::F64(e) => Some(e),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let filled: Vec<Option<SafeType>> = match strategy {
+            FillStrategy::Forward => {
+                let mut last = None;
+                present
+                    .iter()
+                    .map(|x| {
+                        if x.is_some() {
+                            last = x.clone();
                         }
-                    }
-                    Types::F32(e) => {
-                        if e == f32::MAX {
-                            errors.push(format!("'F32' about to be overflow"));
-                            None
-                        } else {
-                            Some(Types::F32(e + 1.))
+                        last.clone()
+                    })
+                    .collect()
+            }
+            FillStrategy::Backward => {
+                let mut next = None;
+                let mut filled: Vec<Option<SafeType>> = present
+                    .iter()
+                    .rev()
+                    .map(|x| {
+                        if x.is_some() {
+                            next = x.clone();
                         }
-                    }
-                    Types::F64(e) => {
-                        if e == f64::MAX {
-                            errors.push(format!("'F64' about to be overflow"));
-                            None
+                        next.clone()
+                    })
+                    .collect();
+                filled.reverse();
+                filled
+            }
+            FillStrategy::Mean | FillStrategy::Median => {
+                let values = present_numbers();
+                let value = if strategy == FillStrategy::Mean {
+                    numeric(values, |x| x.iter().sum::<f64>() / x.len() as f64)?
+                } else {
+                    numeric(values, |x| {
+                        x.sort_by(|a, b| a.total_cmp(b));
+                        let middle = x.len() / 2;
+                        if x.len() % 2 == 0 {
+                            (x[middle - 1] + x[middle]) / 2.
                         } else {
-                            Some(Types::F64(e + 1.))
+                            x[middle]
                         }
-                    }
-                    _ => {
-                        errors.push(format!("{} is not a integer type column", row));
-                        None
-                    }
-                } {
-                    Some(e) => {
-                        entries[header_pos].rtype = e;
-                    }
-                    None => {}
+                    })?
+                };
+                // Nothing to average, cells stay missing
+                if present.iter().all(|x| x.is_none()) {
+                    vec![None; present.len()]
+                } else {
+                    vec![Some(value); present.len()]
+                }
+            }
+            FillStrategy::Constant(value) => {
+                if value.get_type_def() != inner {
+                    return Err(format!(
+                        "Type mismatch, expected {}, got {} on column {}",
+                        inner,
+                        value.get_type_def(),
+                        column
+                    ));
+                }
+                vec![Some(value); present.len()]
+            }
+        };
+
+        let unique_values = self.take_unique_values();
+        self.invalidate_cache();
+        let mut previous = vec![];
+        let mut count = 0;
+        #[cfg(feature = "events")]
+        let mut updated = vec![];
+        for (index, value) in filled.into_iter().enumerate() {
+            if let (None, Some(value)) = (&present[index], value) {
+                if unique_values.is_some() {
+                    previous.push((index, self.columns[index].clone()));
                 }
+                self.columns[index][position] = SafeType::some(value);
+                count += 1;
+                #[cfg(feature = "events")]
+                updated.push(self.columns[index].clone());
+            }
+        }
+        self.check_changed_rows(unique_values, previous)
+            .map_err(|errors| errors.join(", "))?;
+        #[cfg(feature = "events")]
+        for row in updated {
+            self.emit(EventType::Update, &row);
+        }
+        Ok(count)
+    }
+
+    /// Cumulative sum of a numeric column, element `i` is the sum of rows `0..=i`
+    /// ## Arguments
+    /// * `column` - Name of the numeric column
+    /// ## Returns
+    /// * [`Ok<Vec<f64>>`] Running total for each row
+    /// * [`Err<String>`] If column does not exist or is not numeric
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("sales", vec![
+    ///    TableRow::new("item", TypeDefs::String),
+    ///    TableRow::new("amount", TypeDefs::F64),
+    /// ]);
+    /// let table = db.table("sales").unwrap();
+    /// table.insert(vec!["a".into(), 1.5_f64.into()]).unwrap();
+    /// table.insert(vec!["b".into(), 2.0_f64.into()]).unwrap();
+    /// table.insert(vec!["c".into(), 0.5_f64.into()]).unwrap();
+    /// assert_eq!(table.running_sum("amount"), Ok(vec![1.5, 3.5, 4.0]));
+    /// assert!(table.running_sum("item").is_err());
+    /// ```
+    pub fn running_sum(&self, column: &str) -> Result<Vec<f64>, String> {
+        let mut total = 0.;
+        Ok(self
+            .numeric_values(column)?
+            .into_iter()
+            .map(|value| {
+                total += value;
+                total
+            })
+            .collect())
+    }
+
+    /// Simple moving average of a numeric column over the last `window` rows
+    ///
+    /// First `window - 1` rows average every row up to them
+    /// ## Arguments
+    /// * `column` - Name of the numeric column
+    /// * `window` - Number of rows in each average
+    /// ## Returns
+    /// * [`Ok<Vec<f64>>`] Average for each row
+    /// * [`Err<String>`] If column does not exist or is not numeric, or `window` is 0
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("prices", vec![
+    ///    TableRow::new("price", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("prices").unwrap();
+    /// for price in [2_i64, 4, 6, 8] {
+    ///     table.insert(vec![price.into()]).unwrap();
+    /// }
+    /// assert_eq!(table.moving_average("price", 2), Ok(vec![2., 3., 5., 7.]));
+    /// assert_eq!(table.moving_average("price", 3), Ok(vec![2., 3., 4., 6.]));
+    /// assert!(table.moving_average("price", 0).is_err());
+    /// ```
+    pub fn moving_average(&self, column: &str, window: usize) -> Result<Vec<f64>, String> {
+        let values = self.numeric_values(column)?;
+        if window == 0 {
+            return Err("Window size must be greater than 0".to_string());
+        }
+        Ok((0..values.len())
+            .map(|i| {
+                let start = (i + 1).saturating_sub(window);
+                values[start..=i].iter().sum::<f64>() / (i + 1 - start) as f64
+            })
+            .collect())
+    }
+
+    /// Rank rows by the value of a column, equal values share a rank and ranks have no gaps
+    ///
+    /// Unknown columns return no rows
+    /// ## Arguments
+    /// * `column` - Name of the column
+    /// * `order` - [`SortOrder`] of the ranking
+    /// ## Returns
+    /// [`Vec<(usize, usize)>`] Row index and its 1-based rank, in row order
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{SortOrder, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("scores", vec![
+    ///    TableRow::new("score", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("scores").unwrap();
+    /// for score in [30_i64, 10, 30, 20] {
+    ///     table.insert(vec![score.into()]).unwrap();
+    /// }
+    /// assert_eq!(
+    ///     table.rank("score", SortOrder::Descending),
+    ///     vec![(0, 1), (1, 3), (2, 1), (3, 2)]
+    /// );
+    /// assert_eq!(
+    ///     table.rank("score", SortOrder::Ascending),
+    ///     vec![(0, 3), (1, 1), (2, 3), (3, 2)]
+    /// );
+    /// ```
+    pub fn rank(&self, column: &str, order: SortOrder) -> Vec<(usize, usize)> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return vec![],
+        };
+        let mut sorted: Vec<(usize, Types)> = self
+            .columns
+            .iter()
+            .map(|row| row[position].get_type())
+            .enumerate()
+            .collect();
+        sorted.sort_by(|a, b| match order {
+            SortOrder::Ascending => a.1.compare(&b.1),
+            SortOrder::Descending => b.1.compare(&a.1),
+        });
+
+        let mut ranks = vec![0; sorted.len()];
+        let mut rank = 0;
+        for i in 0..sorted.len() {
+            if i == 0 || sorted[i - 1].1.compare(&sorted[i].1) != Ordering::Equal {
+                rank += 1;
+            }
+            ranks[sorted[i].0] = rank;
+        }
+        ranks.into_iter().enumerate().collect()
+    }
+
+    /// Find the first row where `column` equals `value` in a table sorted by `column` in ascending order
+    ///
+    /// Only rows visited by the search are checked for order, so an unsorted table is not always detected
+    /// ## Arguments
+    /// * `column` - Name of the sorted column
+    /// * `value` - Value to find
+    /// ## Returns
+    /// * [`Ok<usize>`] Index of the first matching row
+    /// * [`Err<SearchError>`] If column does not exist, value is not found or column is not sorted
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{SearchError, TableRow, TypeDefs, Types}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("id", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// for id in [1_i64, 3, 3, 5, 8] {
+    ///     table.insert(vec![id.into()]).unwrap();
+    /// }
+    /// assert_eq!(table.binary_search("id", &Types::I64(3)), Ok(1));
+    /// assert_eq!(table.binary_search("id", &Types::I64(8)), Ok(4));
+    /// assert_eq!(table.binary_search("id", &Types::I64(4)), Err(SearchError::NotFound));
+    ///
+    /// table.insert(vec![2_i64.into()]).unwrap();
+    /// assert_eq!(table.binary_search("id", &Types::I64(9)), Err(SearchError::Unsorted));
+    /// ```
+    pub fn binary_search(&self, column: &str, value: &Types) -> Result<usize, SearchError> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return Err(SearchError::ColumnNotFound(column.to_string())),
+        };
+        // Values of another type never match and would compare as equal
+        let column_type = self.headers[position].rtype.default_value().rtype;
+        if std::mem::discriminant(&column_type) != std::mem::discriminant(value) {
+            return Err(SearchError::NotFound);
+        }
+
+        let value_at = |index: usize| &self.columns[index][position].rtype;
+        let mut low = 0;
+        let mut high = self.columns.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if mid > 0 && value_at(mid).compare(value_at(mid - 1)) == Ordering::Less {
+                return Err(SearchError::Unsorted);
+            }
+            if value_at(mid).compare(value) == Ordering::Less {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low < self.columns.len() && value_at(low) == value {
+            Ok(low)
+        } else {
+            Err(SearchError::NotFound)
+        }
+    }
+
+    /// Sort rows in place by a column
+    ///
+    /// Sort is stable. Bool, Array and Tuple columns have no natural ordering and cannot be sorted,
+    /// missing values of Option columns come first in ascending order
+    /// ## Arguments
+    /// * `column` - Name of the sort column
+    /// * `ascending` - Sort smallest value first if `true`, largest first if `false`
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<String>`] If column does not exist or cannot be sorted, table is left untouched
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("players", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("score", TypeDefs::I64),
+    ///    TableRow::new("active", TypeDefs::Bool),
+    /// ]);
+    /// let table = db.table("players").unwrap();
+    /// table.insert(vec!["Cem".into(), 10_i64.into(), true.into()]).unwrap();
+    /// table.insert(vec!["Ali".into(), 20_i64.into(), false.into()]).unwrap();
+    /// table.insert(vec!["Bora".into(), 15_i64.into(), true.into()]).unwrap();
+    ///
+    /// table.sort_by("score", false).unwrap();
+    /// let names: Vec<String> = table.get_all().iter().map(|x| x.get("name").unwrap().get()).collect();
+    /// assert_eq!(names, vec!["Ali", "Bora", "Cem"]);
+    /// assert!(table.sort_by("active", true).is_err());
+    /// assert!(table.sort_by("age", true).is_err());
+    /// ```
+    pub fn sort_by(&mut self, column: &str, ascending: bool) -> Result<(), String> {
+        self.sort_by_multi(&[(column, ascending)])
+    }
+
+    /// Sort rows in place by several columns, later columns only order rows equal on all earlier columns
+    ///
+    /// Sort is stable and columns follow the same rules as [`Table::sort_by`]
+    /// ## Arguments
+    /// * `columns` - Name of each sort column and whether it is sorted ascending, highest priority first
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<String>`] If a column does not exist or cannot be sorted, table is left untouched
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![
+    ///    TableRow::new("first_name", TypeDefs::String),
+    ///    TableRow::new("last_name", TypeDefs::String),
+    ///    TableRow::new("age", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Cem".into(), "Yilmaz".into(), 30_i64.into()]).unwrap();
+    /// table.insert(vec!["Ali".into(), "Yilmaz".into(), 20_i64.into()]).unwrap();
+    /// table.insert(vec!["Bora".into(), "Demir".into(), 20_i64.into()]).unwrap();
+    ///
+    /// table.sort_by_multi(&[("last_name", true), ("first_name", true)]).unwrap();
+    /// let names: Vec<String> = table.get_all().iter().map(|x| x.get("first_name").unwrap().get()).collect();
+    /// assert_eq!(names, vec!["Bora", "Ali", "Cem"]);
+    ///
+    /// table.sort_by_multi(&[("age", false), ("first_name", false)]).unwrap();
+    /// let names: Vec<String> = table.get_all().iter().map(|x| x.get("first_name").unwrap().get()).collect();
+    /// assert_eq!(names, vec!["Cem", "Bora", "Ali"]);
+    /// assert!(table.sort_by_multi(&[("age", true), ("email", true)]).is_err());
+    ///
+    /// // NaN is sorted after every number
+    /// db.create_table("readings", vec![TableRow::new("value", TypeDefs::F64)]).unwrap();
+    /// let readings = db.table("readings").unwrap();
+    /// for value in [2.0, f64::NAN, 1.0, 3.0] {
+    ///     readings.insert(vec![value.into()]).unwrap();
+    /// }
+    /// readings.sort_by_multi(&[("value", true)]).unwrap();
+    /// let values: Vec<f64> = readings.get_all().iter().map(|x| x.get("value").unwrap().get()).collect();
+    /// assert_eq!(values[..3], [1.0, 2.0, 3.0]);
+    /// assert!(values[3].is_nan());
+    /// ```
+    pub fn sort_by_multi(&mut self, columns: &[(&str, bool)]) -> Result<(), String> {
+        fn sortable(rtype: &TypeDefs) -> bool {
+            match rtype {
+                TypeDefs::Bool | TypeDefs::Array(_) | TypeDefs::Tuple(_) => false,
+                TypeDefs::Option(inner) => sortable(inner),
+                _ => true,
+            }
+        }
+        let mut keys = vec![];
+        for (column, ascending) in columns {
+            let position = match self.headers.iter().position(|x| x.key == *column) {
+                Some(e) => e,
+                None => return Err(format!("Could not find key '{}' in table", column)),
+            };
+            if !sortable(&self.headers[position].rtype) {
+                return Err(format!(
+                    "Column '{}' of type {} cannot be sorted",
+                    column, self.headers[position].rtype
+                ));
             }
+            keys.push((position, *ascending));
+        }
+
+        self.invalidate_cache();
+        self.columns.sort_by(|a, b| {
+            keys.iter()
+                .map(|(position, ascending)| {
+                    let ordering = a[*position].rtype.compare(&b[*position].rtype);
+                    if *ascending {
+                        ordering
+                    } else {
+                        ordering.reverse()
+                    }
+                })
+                .find(|x| *x != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(())
+    }
+
+    /// Sort rows in place by `primary` column, rows with equal `primary` values are sorted by `secondary`
+    ///
+    /// Shorthand for [`Table::sort_by_multi`] with two columns, sort is stable so rows equal on both columns keep their order
+    /// ## Arguments
+    /// * `primary` - Name of the first sort column
+    /// * `secondary` - Name of the column breaking ties
+    /// * `order` - [`SortOrder`] applied to both columns
+    /// ## Returns
+    /// * [`Ok<()>`]
+    /// * [`Err<String>`] If a column does not exist or can not be sorted, table is left untouched
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{SortOrder, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("players", vec![
+    ///    TableRow::new("name", TypeDefs::String),
+    ///    TableRow::new("score", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("players").unwrap();
+    /// table.insert(vec!["Cem".into(), 10_i64.into()]).unwrap();
+    /// table.insert(vec!["Ali".into(), 20_i64.into()]).unwrap();
+    /// table.insert(vec!["Bora".into(), 10_i64.into()]).unwrap();
+    ///
+    /// table.stable_sort("score", "name", SortOrder::Ascending).unwrap();
+    /// let names: Vec<String> = table.get_all().iter().map(|x| x.get("name").unwrap().get()).collect();
+    /// assert_eq!(names, vec!["Bora", "Cem", "Ali"]);
+    /// assert!(table.stable_sort("score", "age", SortOrder::Ascending).is_err());
+    /// ```
+    pub fn stable_sort(
+        &mut self,
+        primary: &str,
+        secondary: &str,
+        order: SortOrder,
+    ) -> Result<(), String> {
+        let ascending = order == SortOrder::Ascending;
+        self.sort_by_multi(&[(primary, ascending), (secondary, ascending)])
+    }
+
+    /// Split rows into `n` buckets of nearly equal size by ascending value of a numeric column
+    ///
+    /// Bucket sizes differ by at most one, larger buckets come first.
+    /// Unknown or non numeric columns and an `n` of 0 return no rows
+    /// ## Arguments
+    /// * `column` - Name of the numeric column
+    /// * `n` - Number of buckets
+    /// ## Returns
+    /// [`Vec<(usize, usize)>`] Row index and its bucket from 1 to `n`, in row order
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("scores", vec![
+    ///    TableRow::new("score", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("scores").unwrap();
+    /// for score in [50_i64, 10, 40, 20, 30] {
+    ///     table.insert(vec![score.into()]).unwrap();
+    /// }
+    /// assert_eq!(
+    ///     table.ntile("score", 2),
+    ///     vec![(0, 2), (1, 1), (2, 2), (3, 1), (4, 1)]
+    /// );
+    /// ```
+    pub fn ntile(&self, column: &str, n: usize) -> Vec<(usize, usize)> {
+        let values = match self.numeric_values(column) {
+            Ok(e) => e,
+            Err(_) => return vec![],
+        };
+        if n == 0 {
+            return vec![];
+        }
+        let mut sorted: Vec<(usize, f64)> = values.into_iter().enumerate().collect();
+        sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let size = sorted.len() / n;
+        let larger = sorted.len() % n;
+        let mut buckets = vec![0; sorted.len()];
+        for (position, (index, _)) in sorted.iter().enumerate() {
+            // First `larger` buckets hold `size + 1` rows
+            buckets[*index] = if position < larger * (size + 1) {
+                position / (size + 1) + 1
+            } else {
+                larger + (position - larger * (size + 1)) / size + 1
+            };
+        }
+        buckets.into_iter().enumerate().collect()
+    }
+
+    /// Values of a numeric column for statistics
+    fn stats_values(&self, column: &str) -> Result<Vec<f64>, StatsError> {
+        if !self.has_column(column) {
+            return Err(StatsError::ColumnNotFound(column.to_string()));
+        }
+        self.numeric_values(column)
+            .map_err(|_| StatsError::NotNumeric(column.to_string()))
+    }
+
+    /// Find rows whose value deviates from the column mean by more than `z_threshold` standard deviations
+    ///
+    /// Sample standard deviation is used, a column with the same value on every row has no outliers
+    /// ## Arguments
+    /// * `column` - Name of the numeric column
+    /// * `z_threshold` - Smallest absolute z-score reported
+    /// ## Returns
+    /// * [`Ok<Vec<(usize, f64)>>`] Row index and z-score of each outlier
+    /// * [`Err<String>`] If column does not exist or is not numeric, or table has fewer than 2 rows
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("latency", vec![
+    ///    TableRow::new("ms", TypeDefs::U64),
+    /// ]);
+    /// let table = db.table("latency").unwrap();
+    /// assert!(table.get_outliers("ms", 2.).is_err());
+    /// for ms in [10_u64, 12, 11, 9, 10, 11, 10, 95] {
+    ///     table.insert(vec![ms.into()]).unwrap();
+    /// }
+    /// let outliers = table.get_outliers("ms", 2.).unwrap();
+    /// assert_eq!(outliers.len(), 1);
+    /// assert_eq!(outliers[0].0, 7);
+    /// assert!(outliers[0].1 > 2.);
+    /// ```
+    pub fn get_outliers(
+        &self,
+        column: &str,
+        z_threshold: f64,
+    ) -> Result<Vec<(usize, f64)>, String> {
+        let values = self.numeric_values(column)?;
+        if values.len() < 2 {
+            return Err(format!(
+                "Column '{}' needs at least 2 rows, got {}",
+                column,
+                values.len()
+            ));
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std_dev =
+            (values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.)).sqrt();
+        if std_dev == 0. {
+            return Ok(vec![]);
+        }
+        Ok(values
+            .iter()
+            .map(|x| (x - mean) / std_dev)
+            .enumerate()
+            .filter(|(_, z)| z.abs() > z_threshold)
+            .collect())
+    }
+
+    /// Pearson correlation coefficient of two numeric columns
+    ///
+    /// Result is NaN if either column has the same value on every row
+    /// ## Arguments
+    /// * `col_a` - Name of the first numeric column
+    /// * `col_b` - Name of the second numeric column
+    /// ## Returns
+    /// * [`Ok<f64>`] Correlation between -1 and 1
+    /// * [`Err<StatsError>`] If a column is missing or not numeric, or table has fewer than 2 rows
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{StatsError, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("points", vec![
+    ///    TableRow::new("label", TypeDefs::String),
+    ///    TableRow::new("x", TypeDefs::I64),
+    ///    TableRow::new("y", TypeDefs::F64),
+    /// ]);
+    /// let table = db.table("points").unwrap();
+    /// assert_eq!(table.correlation("x", "y"), Err(StatsError::InsufficientData));
+    /// for x in 0..5_i64 {
+    ///     table.insert(vec!["p".into(), x.into(), (10. - 2. * x as f64).into()]).unwrap();
+    /// }
+    /// assert!((table.correlation("x", "y").unwrap() + 1.).abs() < 1e-9);
+    /// assert_eq!(
+    ///     table.correlation("x", "label"),
+    ///     Err(StatsError::NotNumeric("label".to_string()))
+    /// );
+    /// ```
+    pub fn correlation(&self, col_a: &str, col_b: &str) -> Result<f64, StatsError> {
+        let sums = self.compute_sums(col_a, col_b)?;
+        Ok(sums.ab / (sums.aa * sums.bb).sqrt())
+    }
+
+    /// Sample covariance of two numeric columns, using `n - 1` as denominator
+    /// ## Arguments
+    /// * `col_a` - Name of the first numeric column
+    /// * `col_b` - Name of the second numeric column
+    /// ## Returns
+    /// * [`Ok<f64>`] Covariance
+    /// * [`Err<StatsError>`] If a column is missing or not numeric, or table has fewer than 2 rows
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{StatsError, TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("points", vec![
+    ///    TableRow::new("x", TypeDefs::I64),
+    ///    TableRow::new("y", TypeDefs::I64),
+    /// ]);
+    /// let table = db.table("points").unwrap();
+    /// for (x, y) in [(1_i64, 2_i64), (2, 4), (3, 6)] {
+    ///     table.insert(vec![x.into(), y.into()]).unwrap();
+    /// }
+    /// assert_eq!(table.covariance("x", "y"), Ok(2.));
+    /// assert_eq!(table.covariance("x", "z"), Err(StatsError::ColumnNotFound("z".to_string())));
+    /// ```
+    pub fn covariance(&self, col_a: &str, col_b: &str) -> Result<f64, StatsError> {
+        let sums = self.compute_sums(col_a, col_b)?;
+        Ok(sums.ab / (sums.n - 1.))
+    }
+
+    /// Sums of deviation products from the column means, shared by correlation and covariance
+    fn compute_sums(&self, col_a: &str, col_b: &str) -> Result<DeviationSums, StatsError> {
+        let a = self.stats_values(col_a)?;
+        let b = self.stats_values(col_b)?;
+        if a.len() < 2 {
+            return Err(StatsError::InsufficientData);
+        }
+        let n = a.len() as f64;
+        let mean_a = a.iter().sum::<f64>() / n;
+        let mean_b = b.iter().sum::<f64>() / n;
+        let mut sums = DeviationSums {
+            n,
+            ab: 0.,
+            aa: 0.,
+            bb: 0.,
+        };
+        for (x, y) in a.iter().zip(b.iter()) {
+            sums.ab += (x - mean_a) * (y - mean_b);
+            sums.aa += (x - mean_a) * (x - mean_a);
+            sums.bb += (y - mean_b) * (y - mean_b);
+        }
+        Ok(sums)
+    }
+    /// Find the `top_k` rows whose vector in `column` is closest to `query` by cosine similarity
+    ///
+    /// Compares `query` with every row. Unknown columns or columns that are not `Array(F64)` return no rows,
+    /// rows with a vector of different length or zero magnitude are skipped
+    /// ## Arguments
+    /// * `column` - Name of the `Array(F64)` column holding the vectors
+    /// * `query` - Vector to compare against
+    /// * `top_k` - Maximum number of rows returned
+    /// ## Returns
+    /// [`Vec<(usize, f64)>`] Row index and similarity, most similar first
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("documents", vec![
+    ///    TableRow::new("title", TypeDefs::String),
+    ///    TableRow::new("embedding", TypeDefs::array_of(TypeDefs::F64)),
+    /// ]);
+    /// let table = db.table("documents").unwrap();
+    /// table.insert(vec!["cats".into(), vec![1., 0.].into()]).unwrap();
+    /// table.insert(vec!["dogs".into(), vec![0., 1.].into()]).unwrap();
+    /// table.insert(vec!["pets".into(), vec![1., 1.].into()]).unwrap();
+    ///
+    /// let found = table.search_vector_similarity("embedding", vec![2., 0.], 2);
+    /// assert_eq!(found[0], (0, 1.));
+    /// assert_eq!(found[1].0, 2);
+    /// assert!(table.search_vector_similarity("title", vec![2., 0.], 2).is_empty());
+    /// ```
+    pub fn search_vector_similarity(
+        &self,
+        column: &str,
+        query: Vec<f64>,
+        top_k: usize,
+    ) -> Vec<(usize, f64)> {
+        let position = match self.headers.iter().position(|x| x.key == column) {
+            Some(e) => e,
+            None => return vec![],
+        };
+        if self.headers[position].rtype != TypeDefs::Array(Box::new(TypeDefs::F64)) {
+            return vec![];
         }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(errors)
+        let query_norm = query.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if query_norm == 0. {
+            return vec![];
         }
+        let mut scores: Vec<(usize, f64)> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| {
+                let vector = match row[position].get_type() {
+                    Types::Array(e) => e,
+                    _ => return None,
+                };
+                if vector.len() != query.len() {
+                    return None;
+                }
+                let mut dot = 0.;
+                let mut norm = 0.;
+                for (value, q) in vector.iter().zip(query.iter()) {
+                    let value: f64 = value.get();
+                    dot += value * q;
+                    norm += value * value;
+                }
+                if norm == 0. {
+                    return None;
+                }
+                Some((index, dot / (norm.sqrt() * query_norm)))
+            })
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scores.truncate(top_k);
+        scores
     }
 
-    /// Increase the value of a number by filter
+    /// Write table as CSV, first line holds the column names
+    ///
+    /// Fields containing commas, quotes or line breaks are quoted, missing Option values are empty fields
     /// ## Arguments
-    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// * `writer` - Destination of the CSV text
     /// ## Returns
     /// * [`Ok<()>`]
-    /// * [`Err<Vec<String>>`] - Error messages
+    /// * [`Err<std::io::Error>`] If writer fails
     /// ## Example
-    /// ```rust
-    /// use safe_en::Database;
-    /// use safe_en::table::{TableRow, TypeDefs};
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
     ///    TableRow::new("name", TypeDefs::String),
     ///    TableRow::new("age", TypeDefs::I64),
     /// ]);
-    /// db.table("users").unwrap().inc_where(|x| {
-    ///     x.row("age").exists()
-    /// }, "age");
-    /// //Increases all ages by 1
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["John".into(), 18_i64.into()]).unwrap();
+    ///
+    /// let mut csv = vec![];
+    /// table.export_csv(&mut csv).unwrap();
+    /// assert_eq!(String::from_utf8(csv).unwrap(), "name,age\nJohn,18\n");
     /// ```
-    pub fn push_where<E: Fn(Entries) -> bool + Clone + Sized>(
-        &mut self,
-        filter: E,
-        row: &str,
-        value: SafeType,
-    ) -> Result<(), Vec<String>> {
-        let mut errors = vec![];
-        for entries in &mut self.columns {
-            let fake_entries = Entries {
-                entries: entries
-                    .iter()
-                    .enumerate()
-                    .map(|(ix, value)| Entry {
-                        key: self.headers[ix].key.clone(),
-                        value: value.clone(),
-                    })
-                    .collect(),
-            };
-
-            if filter(fake_entries.clone()) {
-                let header_pos = self.headers.iter().position(|x| x.key == row).unwrap();
-                match match entries[header_pos].clone().rtype {
-                    Types::Array(e) => {
-                        let type_inner_type = entries[header_pos]
-                            .clone()
-                            .get_type_def()
-                            .inner_type()
-                            .unwrap();
-
-                        if value.get_type().is_array() {
-                            let value_inner_type = value.get_type_def().inner_type().unwrap();
-
-                            if type_inner_type == value_inner_type {
-                                let mut arr_copy = e.clone();
-                                let contents = match value.get_type() {
-                                    Types::Array(c) => c,
-                                    _ => unreachable!(),
-                                };
-                                arr_copy.extend(contents);
-                                Some(Types::Array(arr_copy))
-                            } else {
-                                errors.push(format!(
-                                    "Inner type of array is '{}' but given value is '{}'",
-                                    type_inner_type,
-                                    value.get_type()
-                                ));
-                                None
-                            }
-                        } else {
-                            let mut arr_copy = e.clone();
-
-                            if type_inner_type == value.get_type_def() {
-                                arr_copy.push(value.clone());
-                                Some(Types::Array(arr_copy))
-                            } else {
-                                errors.push(format!(
-                                    "Inner type of array is '{}' but given value is '{}'",
-                                    type_inner_type,
-                                    value.get_type()
-                                ));
-                                None
-                            }
-                        }
-                    }
-                    _ => {
-                        errors.push("Field is not a array type".to_string());
-                        None
-                    }
-                } {
-                    Some(e) => {
-                        entries[header_pos].rtype = e;
-                    }
-                    None => (),
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(errors)
+    pub fn export_csv(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        let headers: Vec<String> = self.headers.iter().map(|x| csv_field(&x.key)).collect();
+        writeln!(writer, "{}", headers.join(","))?;
+        for row in &self.columns {
+            let fields: Vec<String> = row
+                .iter()
+                .map(|value| csv_field(&csv_value(&value.rtype)))
+                .collect();
+            writeln!(writer, "{}", fields.join(","))?;
         }
+        Ok(())
     }
 
-    /// Set the value of a column by filter
-    /// ## Arguments
-    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
-    /// * `value` - Value to set
+    /// Get table as a CSV string, same format as [`Table::export_csv`]
     /// ## Returns
-    /// * [`Ok<usize>`] - Effected row length
-    /// * [`Err<Vec<String>>`] - Error messages
+    /// [`String`] CSV text
     /// ## Example
-    /// ```rust
-    /// use safe_en::Database;
-    /// use safe_en::table::{TableRow, TypeDefs};
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
     /// db.create_table("users", vec![
     ///    TableRow::new("name", TypeDefs::String),
-    ///    TableRow::new("age", TypeDefs::I64),
-    /// ]);
-    /// db.table("users").unwrap().set_where(|x| {
-    ///     x.row("name").is("Ahmet".to_string())
-    /// }, vec![
-    ///     safe_en::table::Entry {
-    ///         key: "name".to_string(),
-    ///         value: "Ahmetcan".into(),
-    ///     },
+    ///    TableRow::new("tags", TypeDefs::array_of(TypeDefs::String)),
     /// ]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec!["Doe, John".into(), vec!["admin"].into()]).unwrap();
+    /// assert_eq!(table.to_csv_string(), "name,tags\n\"Doe, John\",\"[\"\"admin\"\"]\"\n");
     /// ```
-    pub fn set_where<E: Fn(Entries) -> bool + Clone + Sized, T>(
-        &mut self,
-        filter: E,
-        value: Vec<Entry>,
-    ) -> Result<usize, Vec<String>>
-    where
-        Types: From<T>,
-        T: Clone,
-    {
-        let mut changed_rows = 0;
-        let mut errors = vec![];
-        if value.len() > self.headers.len() {
-            errors.push("Value length is not equal to header length".to_string());
-            return Err(errors);
-        }
-        'entryloop: for entries in &mut self.columns {
-            let fake_entries = Entries {
-                entries: entries
-                    .iter()
-                    .enumerate()
-                    .map(|(ix, value)| Entry {
-                        key: self.headers[ix].key.clone(),
-                        value: value.clone(),
-                    })
-                    .collect(),
-            };
+    pub fn to_csv_string(&self) -> String {
+        let mut bytes = vec![];
+        self.export_csv(&mut bytes)
+            .expect("Writing to a Vec can not fail");
+        String::from_utf8(bytes).expect("CSV of a table is valid UTF-8")
+    }
+}
 
-            if filter(fake_entries.clone()) {
-                for value_entry in value.iter() {
-                    let targt = fake_entries
-                        .entries
-                        .iter()
-                        .find(|x| x.key == value_entry.key);
-                    if let Some(target) = targt {
-                        if target.value.get_type_def() == value_entry.value.get_type_def() {
-                            let header_pos = self
-                                .headers
-                                .iter()
-                                .position(|x| x.key == value_entry.key)
-                                .unwrap();
-                            changed_rows += 1;
-                            entries[header_pos] = value_entry.value.clone();
-                        } else {
-                            errors.push(format!(
-                                "Value type is not equal to header type. Header: {}, Value: {}",
-                                target.value.get_type_def(),
-                                value_entry.value.get_type_def()
-                            ));
-                            break 'entryloop;
-                        }
-                    } else {
-                        errors.push(format!("Could not find key '{}' in table", value_entry.key));
-                        break 'entryloop;
-                    }
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(changed_rows)
-        } else {
-            Err(errors)
-        }
+/// Value of a CSV field, scalars are written without type suffixes
+fn csv_value(value: &Types) -> String {
+    match value {
+        Types::String(e) => e.clone(),
+        Types::Char(e) => e.to_string(),
+        Types::I8(e) => e.to_string(),
+        Types::I64(e) => e.to_string(),
+        Types::U64(e) => e.to_string(),
+        Types::Bool(e) => e.to_string(),
+        Types::F32(e) => e.to_string(),
+        Types::F64(e) => e.to_string(),
+        Types::Option(Some(e)) => csv_value(&e.rtype),
+        Types::Option(None) => String::new(),
+        Types::Array(_) | Types::Tuple(_) => format!("{}", value),
     }
+}
 
-    /// Insert data to table
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Lazy iterator over the rows of a [`Table`], returned by [`Table::iter`]
+pub struct Rows<'a> {
+    table: &'a Table,
+    index: usize,
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Entries;
+
+    fn next(&mut self) -> Option<Entries> {
+        let entries = self.table.row_at(self.index)?;
+        self.index += 1;
+        Some(entries)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.table.columns.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Rows<'a> {}
+
+/// Paged view of a table, returned by [`Table::skip`]
+pub struct SkipTake<'a> {
+    table: &'a Table,
+    skip: usize,
+    take: Option<usize>,
+    filter: Option<Box<dyn Fn(Entries) -> bool + 'a>>,
+}
+
+impl<'a> SkipTake<'a> {
+    /// Limit the view to `n` rows
     /// ## Arguments
-    /// * `rows` - [`TableRow`]
-    /// ## Returns
-    /// * [`Result<()>`]
-    /// * [`Err<Vec<String>>`] for insert errors
+    /// * `n` - Maximum number of rows to return
     /// ## Example
     /// ```
-    /// use safe_en::{table::{TableRow, TypeDefs, Types},Database};
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
     /// let mut db = Database::new();
-    /// db.create_table(
-    ///    "test",
-    ///   vec![
-    ///      TableRow::new("name", TypeDefs::String),
-    ///     TableRow::new("age", TypeDefs::I64),
-    /// ]).unwrap();
-    ///
-    /// db.table("test").unwrap()
-    ///    .insert(vec![
-    ///       "John".into(),
-    ///      18_i64.into(),
-    ///     ]).unwrap();
+    /// db.create_table("users", vec![TableRow::new("age", TypeDefs::I64)]);
+    /// let table = db.table("users").unwrap();
+    /// for age in 0..10_i64 {
+    ///     table.insert(vec![age.into()]).unwrap();
+    /// }
+    /// assert_eq!(table.skip(2).take(3).get().len(), 3);
     /// ```
-    pub fn insert(&mut self, rows: Vec<SafeType>) -> Result<(), Vec<String>> {
-        let mut errors = vec![];
-        if rows.len() != self.headers.len() {
-            errors.push(format!(
-                "Length mismatch, expected {}, got {} length of column",
-                rows.len(),
-                self.headers.len()
-            ));
-            return Err(errors);
-        }
-        let mut _rows = vec![];
+    pub fn take(mut self, n: usize) -> Self {
+        self.take = Some(n);
+        self
+    }
 
-        for i in 0..rows.len() {
-            let header = &self.headers[i];
-            let rtype: SafeType = rows[i].clone().into();
-            if header.rtype == rtype.get_type_def() {
-                _rows.push(rtype);
-            } else {
-                errors.push(format!(
-                    "Type mismatch, expected {}, got {} on column {}",
-                    header.rtype,
-                    rtype.get_type_def(),
-                    i
-                ));
-            }
-        }
+    /// Only count rows matching `filter`, skipping and taking apply to matching rows
+    /// ## Arguments
+    /// * `filter` - Filter function [`Fn(`Entry`) -> bool`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![TableRow::new("age", TypeDefs::I64)]);
+    /// let table = db.table("users").unwrap();
+    /// for age in 0..10_i64 {
+    ///     table.insert(vec![age.into()]).unwrap();
+    /// }
+    /// let even = table
+    ///     .skip(1)
+    ///     .take(2)
+    ///     .filter(|x| x.get("age").unwrap().get::<i64>() % 2 == 0)
+    ///     .get();
+    /// assert_eq!(even[0].get("age").unwrap().get::<i64>(), 2);
+    /// assert_eq!(even[1].get("age").unwrap().get::<i64>(), 4);
+    /// ```
+    pub fn filter<E: Fn(Entries) -> bool + 'a>(mut self, filter: E) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
 
-        self.columns.push(_rows);
-        if errors.len() > 0 {
-            Err(errors)
-        } else {
-            Ok(())
+    /// Get rows of the view
+    /// ## Returns
+    /// * [`Vec<Entries>`]
+    /// ## Example
+    /// ```
+    /// use safe_en::{Database, table::{TableRow, TypeDefs}};
+    /// let mut db = Database::new();
+    /// db.create_table("users", vec![TableRow::new("age", TypeDefs::I64)]);
+    /// let table = db.table("users").unwrap();
+    /// table.insert(vec![1_i64.into()]).unwrap();
+    /// assert_eq!(table.skip(0).get().len(), 1);
+    /// ```
+    pub fn get(&self) -> Vec<Entries> {
+        let rows = self
+            .table
+            .iter()
+            .filter(|x| match &self.filter {
+                Some(filter) => filter(x.clone()),
+                None => true,
+            })
+            .skip(self.skip);
+        match self.take {
+            Some(n) => rows.take(n).collect(),
+            None => rows.collect(),
         }
     }
 }
@@ -1,6 +1,14 @@
 #![allow(unused_variables)]
-use crate::table::{SafeType, TypeDefs, Types};
-use std::{fs::File, io::Read};
+use crate::{
+    table::{SafeType, TypeDefs, Types},
+    LoadError,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::Hash,
+    io::Read,
+    sync::{Mutex, MutexGuard},
+};
 
 #[derive(Debug)]
 pub(crate) struct RawType {
@@ -8,103 +16,159 @@ pub(crate) struct RawType {
     pub type_data: Vec<u8>,
 }
 
-pub(crate) fn read_one(data: &mut File) -> i8 {
-    let mut buffer = [0; 1];
-    data.read_exact(&mut buffer).unwrap();
-    buffer[0] as i8
+/// Written at the start of every file, followed by [`FORMAT_VERSION`]
+pub(crate) const FILE_MAGIC: [u8; 4] = *b"SFEN";
+/// Version of the file layout, 2 since strings and arrays have u64 length prefixes
+pub(crate) const FORMAT_VERSION: u8 = 2;
+
+/// Reads the magic and format version, failing for files of another layout
+pub(crate) fn read_file_header(data: &mut impl Read) -> Result<(), LoadError> {
+    if read_bytes::<4>(data)? != FILE_MAGIC || read_one(data)? as u8 != FORMAT_VERSION {
+        return Err(LoadError);
+    }
+    Ok(())
 }
 
-pub(crate) fn read_data(data: &mut File, rtype: TypeDefs) -> SafeType {
-    match rtype {
+pub(crate) fn read_one(data: &mut impl Read) -> Result<i8, LoadError> {
+    Ok(read_bytes::<1>(data)?[0] as i8)
+}
+
+/// Reads exactly `N` bytes, failing on truncated input
+fn read_bytes<const N: usize>(data: &mut impl Read) -> Result<[u8; N], LoadError> {
+    let mut buffer = [0; N];
+    data.read_exact(&mut buffer).map_err(|_| LoadError)?;
+    Ok(buffer)
+}
+
+/// Reads a u64 length prefix, failing if it does not fit in usize on this target
+fn read_len(data: &mut impl Read) -> Result<usize, LoadError> {
+    usize::try_from(u64::from_le_bytes(read_bytes(data)?)).map_err(|_| LoadError)
+}
+
+pub(crate) fn read_data(data: &mut impl Read, rtype: TypeDefs) -> Result<SafeType, LoadError> {
+    let value: Types = match rtype {
         TypeDefs::String => {
-            let header_size = read_one(data);
-            let header_size: i8 = header_size.into();
-            let mut header = vec![0; header_size as usize];
-            data.read_exact(&mut header).unwrap();
-            let mut str_buffer = vec![0; header[0] as usize];
-            data.read_exact(&mut str_buffer).unwrap();
-            let st = String::from_utf8(str_buffer).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: st.into(),
+            read_one(data)?;
+            let str_size = read_len(data)?;
+            // Length prefix is not trusted for the allocation, a truncated file ends the read early
+            let mut str_buffer = vec![];
+            data.take(str_size as u64)
+                .read_to_end(&mut str_buffer)
+                .map_err(|_| LoadError)?;
+            if str_buffer.len() != str_size {
+                return Err(LoadError);
             }
+            String::from_utf8(str_buffer).map_err(|_| LoadError)?.into()
         }
         TypeDefs::Char => {
-            read_one(data);
-            let mut header = [0; 4];
-            data.read_exact(&mut header).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: char::from_u32(u32::from_le_bytes(header)).unwrap().into(),
-            }
+            // Size prefix written by `extend_bytes_from_raw_type`, always 4 for a u32 code point
+            read_one(data)?;
+            char::from_u32(u32::from_le_bytes(read_bytes(data)?))
+                .ok_or(LoadError)?
+                .into()
         }
         TypeDefs::I8 => {
-            let mut buffer = [0; 2];
-            data.read_exact(&mut buffer).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: (buffer[1] as i8).into(),
+            let type_size = read_one(data)?;
+            if type_size as usize != core::mem::size_of::<i8>() {
+                return Err(LoadError);
             }
+            read_one(data)?.into()
         }
         TypeDefs::I64 => {
-            read_one(data);
-            let mut header = [0; 8];
-            data.read_exact(&mut header).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: i64::from_le_bytes(header).into(),
-            }
+            read_one(data)?;
+            i64::from_le_bytes(read_bytes(data)?).into()
         }
         TypeDefs::U64 => {
-            read_one(data);
-            let mut header = [0; 8];
-            data.read_exact(&mut header).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: u64::from_le_bytes(header).into(),
-            }
-        }
-        TypeDefs::Bool => {
-            let mut buffer = [0; 2];
-            data.read_exact(&mut buffer).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: (buffer[1] == 1).into(),
-            }
+            read_one(data)?;
+            u64::from_le_bytes(read_bytes(data)?).into()
         }
+        TypeDefs::Bool => (read_bytes::<2>(data)?[1] == 1).into(),
         TypeDefs::F32 => {
-            read_one(data);
-            let mut header = [0; 4];
-            data.read_exact(&mut header).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: (f32::from_le_bytes(header)).into(),
-            }
+            read_one(data)?;
+            f32::from_le_bytes(read_bytes(data)?).into()
         }
         TypeDefs::F64 => {
-            read_one(data);
-            let mut header = [0; 8];
-            data.read_exact(&mut header).unwrap();
-            SafeType {
-                type_id: rtype,
-                rtype: (f64::from_le_bytes(header)).into(),
+            read_one(data)?;
+            f64::from_le_bytes(read_bytes(data)?).into()
+        }
+        TypeDefs::Option(ref e) => {
+            let value = if read_one(data)? == 1 {
+                Some(Box::new(read_data(data, *e.clone())?))
+            } else {
+                None
+            };
+            Types::Option(value)
+        }
+        TypeDefs::Tuple(ref e) => {
+            // Size prefix is the element count, it must match the header
+            if read_one(data)? as u8 as usize != e.len() {
+                return Err(LoadError);
             }
+            Types::Tuple(
+                e.iter()
+                    .map(|x| read_data(data, x.clone()))
+                    .collect::<Result<_, _>>()?,
+            )
         }
         TypeDefs::Array(ref e) => {
-            read_one(data);
-            let mut header = [0; 8];
-            data.read_exact(&mut header).unwrap();
-            let array_size = usize::from_le_bytes(header);
-            let mut array = Vec::with_capacity(array_size);
+            read_one(data)?;
+            let array_size = read_len(data)?;
+            // Capacity is capped so a corrupted length prefix can not allocate before reading fails
+            let mut array = Vec::with_capacity(array_size.min(1024));
             for _ in 0..array_size {
-                let data = read_data(data, *e.clone());
-                array.push(data);
-            }
-            SafeType {
-                type_id: rtype,
-                rtype: Types::Array(array),
+                array.push(read_data(data, *e.clone())?);
             }
+            Types::Array(array)
+        }
+    };
+    Ok(SafeType {
+        type_id: rtype,
+        rtype: value,
+    })
+}
+
+/// Set on the first type id byte of a header when the column is unique
+pub(crate) const UNIQUE_FLAG: u8 = 0x80;
+/// Set on the first type id byte of a header when a default value follows the type id
+pub(crate) const DEFAULT_FLAG: u8 = 0x40;
+
+/// Reads the type id of a table header, returns the type and the constraint flags set on it
+pub(crate) fn read_header_type(data: &mut impl Read) -> Result<(TypeDefs, u8), LoadError> {
+    let first = read_one(data)? as u8;
+    let flags = first & (UNIQUE_FLAG | DEFAULT_FLAG);
+    Ok((read_type_def_from(first & !flags, data)?, flags))
+}
+
+/// Reads a type id written by `TypeDefs::get_base_and_second_layer`
+pub(crate) fn read_type_def(data: &mut impl Read) -> Result<TypeDefs, LoadError> {
+    let base = read_one(data)? as u8;
+    read_type_def_from(base, data)
+}
+
+/// Reads the rest of a type id whose first byte is `base`
+fn read_type_def_from(base: u8, data: &mut impl Read) -> Result<TypeDefs, LoadError> {
+    if base == 10 {
+        return Ok(TypeDefs::option_of(read_type_def(data)?));
+    }
+    let second_layer = read_one(data)? as u8;
+    let read_tuple = |data: &mut _, len: u8| -> Result<TypeDefs, LoadError> {
+        Ok(TypeDefs::Tuple(
+            (0..len)
+                .map(|_| read_type_def(data))
+                .collect::<Result<_, _>>()?,
+        ))
+    };
+    match (base, second_layer) {
+        (9, len) => read_tuple(data, len),
+        (8, 10) => Ok(TypeDefs::array_of(TypeDefs::option_of(read_type_def(
+            data,
+        )?))),
+        (8, 9) => {
+            let len = read_one(data)? as u8;
+            Ok(TypeDefs::array_of(read_tuple(data, len)?))
         }
+        (0..=7, _) | (8, 0..=7) => Ok(TypeDefs::from_base_and_second_layer(base, second_layer)),
+        _ => Err(LoadError),
     }
 }
 
@@ -123,8 +187,8 @@ where
     let mut type_data = Vec::new();
     match rtype {
         Types::String(data) => {
-            _type_size = core::mem::size_of::<usize>();
-            type_data.extend(data.len().to_le_bytes().to_vec());
+            _type_size = core::mem::size_of::<u64>();
+            type_data.extend((data.len() as u64).to_le_bytes().to_vec());
             type_data.extend_from_slice(data.as_bytes());
         }
         Types::Char(data) => {
@@ -156,8 +220,23 @@ where
             type_data = data.to_le_bytes().to_vec();
         }
         Types::Array(data) => {
-            _type_size = core::mem::size_of::<usize>();
-            type_data = data.len().to_le_bytes().to_vec();
+            // Array length is always stored as u64 so files stay portable between 32-bit and 64-bit targets
+            _type_size = core::mem::size_of::<u64>();
+            type_data = (data.len() as u64).to_le_bytes().to_vec();
+            for e in data {
+                extend_bytes_from_raw_type(&mut type_data, &type_to_bytes(e.get_type()));
+            }
+        }
+        Types::Option(data) => {
+            // Size prefix is 1 when a value follows
+            _type_size = data.is_some() as usize;
+            if let Some(e) = data {
+                extend_bytes_from_raw_type(&mut type_data, &type_to_bytes(e.get_type()));
+            }
+        }
+        Types::Tuple(data) => {
+            // Element types come from the header, size prefix only records element count
+            _type_size = data.len();
             for e in data {
                 extend_bytes_from_raw_type(&mut type_data, &type_to_bytes(e.get_type()));
             }
@@ -168,3 +247,244 @@ where
         type_data,
     }
 }
+
+/// Serialized bytes of a value, equal values have equal bytes
+///
+/// `-0.0` is written as `0.0` since both compare equal, NaN never equals itself so its bytes are left as is
+pub(crate) fn value_bytes(value: &SafeType) -> Vec<u8> {
+    let mut bytes = vec![];
+    extend_bytes_from_raw_type(
+        &mut bytes,
+        &type_to_bytes(without_negative_zero(value.get_type())),
+    );
+    bytes
+}
+
+fn without_negative_zero(rtype: Types) -> Types {
+    let inner = |value: SafeType| SafeType {
+        rtype: without_negative_zero(value.rtype),
+        type_id: value.type_id,
+    };
+    match rtype {
+        // Float patterns compare with `==`, so these also match -0.0
+        Types::F32(0.0) => Types::F32(0.0),
+        Types::F64(0.0) => Types::F64(0.0),
+        Types::Array(e) => Types::Array(e.into_iter().map(inner).collect()),
+        Types::Tuple(e) => Types::Tuple(e.into_iter().map(inner).collect()),
+        Types::Option(e) => Types::Option(e.map(|x| Box::new(inner(*x)))),
+        rtype => rtype,
+    }
+}
+
+/// FNV-1a 64 bit hash
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[derive(Debug, Clone)]
+struct LruState<K, V> {
+    /// Value and the generation of its last use
+    values: HashMap<K, (V, u64)>,
+    /// Keys by generation of their last use, first entry is the least recently used
+    order: BTreeMap<u64, K>,
+    next_generation: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruState<K, V> {
+    /// Mark `key` as most recently used, `previous` is the generation of its last use
+    fn touch(&mut self, key: K, previous: Option<u64>) -> u64 {
+        if let Some(previous) = previous {
+            self.order.remove(&previous);
+        }
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        self.order.insert(generation, key);
+        generation
+    }
+}
+
+/// Least recently used cache, keeps at most `capacity` values
+#[derive(Debug)]
+pub(crate) struct LruCache<K, V> {
+    capacity: usize,
+    state: Mutex<LruState<K, V>>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity,
+            state: Mutex::new(LruState {
+                values: HashMap::with_capacity(capacity),
+                order: BTreeMap::new(),
+                next_generation: 0,
+            }),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &K) -> Option<V> {
+        let mut state = self.state.lock().unwrap();
+        let (value, previous) = state.values.get(key).cloned()?;
+        let generation = state.touch(key.clone(), Some(previous));
+        if let Some(entry) = state.values.get_mut(key) {
+            entry.1 = generation;
+        }
+        Some(value)
+    }
+
+    pub(crate) fn put(&self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        let previous = state.values.get(&key).map(|x| x.1);
+        if previous.is_none() && state.values.len() == self.capacity {
+            if let Some((&oldest, _)) = state.order.iter().next() {
+                if let Some(oldest) = state.order.remove(&oldest) {
+                    state.values.remove(&oldest);
+                }
+            }
+        }
+        let generation = state.touch(key.clone(), previous);
+        state.values.insert(key, (value, generation));
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.values.clear();
+        state.order.clear();
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for LruCache<K, V> {
+    fn clone(&self) -> Self {
+        LruCache {
+            capacity: self.capacity,
+            state: Mutex::new(self.state.lock().unwrap().clone()),
+        }
+    }
+}
+
+/// Bloom filter over value bytes, answers "definitely absent" or "maybe present"
+#[derive(Debug, Clone)]
+pub(crate) struct BloomIndex {
+    expected_items: usize,
+    false_positive_rate: f64,
+    num_bits: u64,
+    num_hashes: u32,
+    bits: Vec<u64>,
+    stale: bool,
+}
+
+impl BloomIndex {
+    /// Size the filter for `expected_items` values at `false_positive_rate`
+    pub(crate) fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let items = expected_items.max(1) as f64;
+        let ln2 = core::f64::consts::LN_2;
+        let num_bits = (-items * false_positive_rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / items) * ln2).round().max(1.0) as u32;
+        BloomIndex {
+            expected_items,
+            false_positive_rate,
+            num_bits,
+            num_hashes,
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            stale: false,
+        }
+    }
+
+    fn positions(&self, bytes: &[u8]) -> impl Iterator<Item = u64> {
+        let hash = fnv1a_64(bytes);
+        let (first, second) = (hash & 0xffffffff, (hash >> 32) | 1);
+        let num_bits = self.num_bits;
+        (0..self.num_hashes as u64)
+            .map(move |i| first.wrapping_add(i.wrapping_mul(second)) % num_bits)
+    }
+
+    /// Replace contents with `values`, growing the filter if there are more values than it was sized for
+    pub(crate) fn rebuild<'a>(&mut self, values: impl ExactSizeIterator<Item = &'a SafeType>) {
+        if values.len() > self.expected_items {
+            *self = BloomIndex::new(values.len() * 2, self.false_positive_rate);
+        }
+        self.bits.iter_mut().for_each(|x| *x = 0);
+        for value in values {
+            for position in self.positions(&value_bytes(value)).collect::<Vec<_>>() {
+                self.bits[(position / 64) as usize] |= 1 << (position % 64);
+            }
+        }
+        self.stale = false;
+    }
+
+    /// Whether contents no longer match the table and need a [`BloomIndex::rebuild`]
+    pub(crate) fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    pub(crate) fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// `false` if value was never added, `true` if it may have been
+    pub(crate) fn might_contain(&self, value: &SafeType) -> bool {
+        self.positions(&value_bytes(value))
+            .all(|position| self.bits[(position / 64) as usize] & (1 << (position % 64)) != 0)
+    }
+}
+
+/// Bloom filters of a table by column name, lockable from shared references so filters can be built lazily
+#[derive(Debug, Default)]
+pub(crate) struct BloomIndexes {
+    filters: Mutex<HashMap<String, BloomIndex>>,
+}
+
+impl BloomIndexes {
+    pub(crate) fn lock(&self) -> MutexGuard<'_, HashMap<String, BloomIndex>> {
+        self.filters.lock().unwrap()
+    }
+}
+
+impl Clone for BloomIndexes {
+    fn clone(&self) -> Self {
+        BloomIndexes {
+            filters: Mutex::new(self.lock().clone()),
+        }
+    }
+}
+
+/// Serialized values of the unique columns of a table, by column position
+pub(crate) type UniqueSets = BTreeMap<usize, HashSet<Vec<u8>>>;
+
+/// [`UniqueSets`] of a table, emptied by every change and put back by writes that keep the sets up to date
+#[derive(Debug, Default)]
+pub(crate) struct UniqueIndex {
+    sets: Mutex<Option<UniqueSets>>,
+}
+
+impl UniqueIndex {
+    pub(crate) fn take(&self) -> Option<UniqueSets> {
+        self.sets.lock().unwrap().take()
+    }
+
+    pub(crate) fn put(&self, sets: UniqueSets) {
+        *self.sets.lock().unwrap() = Some(sets);
+    }
+
+    pub(crate) fn clear(&self) {
+        *self.sets.lock().unwrap() = None;
+    }
+}
+
+impl Clone for UniqueIndex {
+    fn clone(&self) -> Self {
+        UniqueIndex {
+            sets: Mutex::new(self.sets.lock().unwrap().clone()),
+        }
+    }
+}
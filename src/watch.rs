@@ -0,0 +1,131 @@
+use crate::{Database, LoadError};
+use std::{
+    fs,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, RwLock,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+/// Default poll interval of [`Database::watch`]
+pub const DEFAULT_INTERVAL_MS: u64 = 500;
+
+/// Options of [`Database::watch_with_options`]
+pub struct WatchOptions {
+    /// Milliseconds between modification time checks
+    pub interval_ms: u64,
+    /// Called with the new state after each successful reload
+    pub on_reload: Box<dyn Fn(&Database) + Send>,
+    /// Called when the changed file fails to load, once for each change of the file
+    pub on_error: Box<dyn Fn(LoadError) + Send>,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            interval_ms: DEFAULT_INTERVAL_MS,
+            on_reload: Box::new(|_| ()),
+            on_error: Box::new(|_| ()),
+        }
+    }
+}
+
+/// Handle of a watched database file, returned by [`Database::watch`]
+///
+/// Dropping the handle stops the background thread
+pub struct WatchHandle {
+    database: Arc<RwLock<Database>>,
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WatchHandle {
+    /// Returns the latest loaded state of the database
+    /// ## Example
+    /// ```
+    /// use safe_en::Database;
+    /// let path = std::env::temp_dir().join("safe_en_watch_current.sfn");
+    /// let path = path.to_str().unwrap();
+    /// let mut db = Database::new();
+    /// db.set_name("app");
+    /// db.save(path).unwrap();
+    ///
+    /// let handle = Database::watch(path).unwrap();
+    /// assert_eq!(handle.current().read().unwrap().get_name(), "app");
+    /// ```
+    pub fn current(&self) -> Arc<RwLock<Database>> {
+        self.database.clone()
+    }
+
+    /// Starts polling `path`, reloading into `database` when modification time or size changes
+    pub(crate) fn spawn(
+        path: &str,
+        database: Database,
+        options: WatchOptions,
+    ) -> Result<WatchHandle, LoadError> {
+        let interval = Duration::from_millis(options.interval_ms);
+        let path = path.to_string();
+        let mut last_state = file_state(&path)?;
+        let mut failed_state = None;
+        let database = Arc::new(RwLock::new(database));
+        let (stop, stop_receiver) = mpsc::channel::<()>();
+
+        let shared = database.clone();
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(interval) {
+                let state = match file_state(&path) {
+                    Ok(e) => e,
+                    Err(_) => continue,
+                };
+                if state == last_state {
+                    continue;
+                }
+                // A file caught mid-write can fail to parse, it is retried on next poll
+                // but the error is reported once per change of the file
+                match Database::load(&path) {
+                    Ok(db) => {
+                        last_state = state;
+                        failed_state = None;
+                        if let Ok(mut current) = shared.write() {
+                            *current = db;
+                        }
+                        // Write guard is dropped first so the callback can read through `WatchHandle::current`
+                        if let Ok(current) = shared.read() {
+                            (options.on_reload)(&current);
+                        }
+                    }
+                    Err(e) => {
+                        if failed_state != Some(state) {
+                            failed_state = Some(state);
+                            (options.on_error)(e);
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(WatchHandle {
+            database,
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Modification time and size of a file, size catches saves within the same modification time tick
+fn file_state(path: &str) -> Result<(SystemTime, u64), LoadError> {
+    fs::metadata(path)
+        .and_then(|x| Ok((x.modified()?, x.len())))
+        .map_err(|_| LoadError)
+}